@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use crate::error::{CodegenError, Result};
 
@@ -27,6 +28,13 @@ pub struct SchemaConfig {
 pub struct QueriesConfig {
     pub path: Option<PathBuf>,
     pub src: Option<Vec<PathBuf>>,
+    /// The DEFINE ACCESS method queries run under, which determines `$auth`.
+    pub access: Option<String>,
+    /// Custom `$token` claims of the access method, as SurrealQL types.
+    pub claims: Option<BTreeMap<String, String>>,
+    /// The role (`viewer`, `editor` or `owner`) of the system user queries run
+    /// as, when they don't run under an access method.
+    pub role: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -18,9 +18,15 @@ pub enum CodegenError {
     #[error("Analysis error: {0}")]
     Analyzer(#[from] surrealguard_core::analyzer::error::AnalyzerError),
 
+    #[error("Invalid role: {0} (expected viewer, editor or owner)")]
+    InvalidRole(String),
+
     #[error("Format error: {0}")]
     Format(String),
 
+    #[error("Generated name {0} is used by both {1} and {2}")]
+    NameCollision(String, String, String),
+
     #[error("Language {0} not implemented")]
     LanguageNotImplemented(String),
 
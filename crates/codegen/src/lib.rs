@@ -48,21 +48,21 @@ pub fn watch(config: &Config) -> Result<()> {
     let mut watcher = RecommendedWatcher::new(
         tx,
         NotifyConfig::default()
-    ).map_err(|e| CodegenError::Watch(e))?;
+    ).map_err(CodegenError::Watch)?;
 
     // Watch directories setup...
     watcher.watch(&config.schema.path, RecursiveMode::Recursive)
-        .map_err(|e| CodegenError::Watch(e))?;
+        .map_err(CodegenError::Watch)?;
 
     if let Some(queries_path) = &config.queries.path {
         watcher.watch(queries_path, RecursiveMode::Recursive)
-            .map_err(|e| CodegenError::Watch(e))?;
+            .map_err(CodegenError::Watch)?;
     }
 
     if let Some(src_dirs) = &config.queries.src {
         for dir in src_dirs {
             watcher.watch(dir, RecursiveMode::Recursive)
-                .map_err(|e| CodegenError::Watch(e))?;
+                .map_err(CodegenError::Watch)?;
         }
     }
 
@@ -1,11 +1,15 @@
 use crate::config::Config;
 use crate::error::{CodegenError, Result};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 use regex::Regex;
 use walkdir::WalkDir;
-use surrealguard_core::analyzer::{self, context::AnalyzerContext};
+use surrealguard_core::analyzer::{
+    self,
+    context::{AnalyzerContext, Role},
+    kind::parse_kind,
+};
 use surrealdb::sql::{Kind, Literal};
 
 pub struct Generator {
@@ -41,6 +45,8 @@ impl Generator {
     pub fn check(&mut self, config: &Config) -> Result<()> {
         self.load_schema(&config.schema.path)?;
 
+        self.load_access(config)?;
+
         if let Some(queries_path) = &config.queries.path {
             self.process_queries(queries_path)?;
         }
@@ -55,6 +61,8 @@ impl Generator {
     pub fn generate(&mut self, config: &Config) -> Result<()> {
         self.load_schema(&config.schema.path)?;
 
+        self.load_access(config)?;
+
         if let Some(queries_path) = &config.queries.path {
             self.process_queries(queries_path)?;
         }
@@ -68,9 +76,10 @@ impl Generator {
 
     fn load_schema(&mut self, path: &Path) -> Result<()> {
         if path.is_dir() {
-            for entry in WalkDir::new(path) {
+            // Schema folders may hold migrations, which must apply in order.
+            for entry in WalkDir::new(path).sort_by_file_name() {
                 let entry = entry.map_err(|_| CodegenError::InvalidPath(path.to_path_buf()))?;
-                if entry.path().extension().map_or(false, |ext| ext == "surql") {
+                if entry.path().extension().is_some_and(|ext| ext == "surql") {
                     let content = fs::read_to_string(entry.path())?;
                     analyzer::analyze(&mut self.ctx, &content)?;
                 }
@@ -82,11 +91,38 @@ impl Generator {
         Ok(())
     }
 
+    fn load_access(&mut self, config: &Config) -> Result<()> {
+        if let Some(role) = &config.queries.role {
+            let role = match role.to_lowercase().as_str() {
+                "viewer" => Role::Viewer,
+                "editor" => Role::Editor,
+                "owner" => Role::Owner,
+                _ => return Err(CodegenError::InvalidRole(role.clone())),
+            };
+            self.ctx.set_role(role);
+        }
+
+        let Some(access) = &config.queries.access else {
+            return Ok(());
+        };
+
+        if let Some(claims) = &config.queries.claims {
+            let claims = claims
+                .iter()
+                .map(|(name, kind)| Ok((name.clone(), parse_kind(kind)?)))
+                .collect::<Result<Vec<_>>>()?;
+            self.ctx.register_claims(access, claims)?;
+        }
+
+        self.ctx.set_access(access)?;
+        Ok(())
+    }
+
     fn process_queries(&mut self, path: &Path) -> Result<()> {
         if path.is_dir() {
             for entry in WalkDir::new(path) {
                 let entry = entry.map_err(|_| CodegenError::InvalidPath(path.to_path_buf()))?;
-                if entry.path().extension().map_or(false, |ext| ext == "surql") {
+                if entry.path().extension().is_some_and(|ext| ext == "surql") {
                     self.process_query_file(entry.path())?;
                 }
             }
@@ -126,25 +162,22 @@ impl Generator {
             for entry in WalkDir::new(dir) {
                 let entry = entry.map_err(|_| CodegenError::InvalidPath(dir.clone()))?;
                 if let Some(ext) = entry.path().extension() {
-                    match ext.to_str() {
-                        Some("ts" | "js" | "jsx" | "tsx" | "svelte" | "vue") => {
-                            let content = fs::read_to_string(entry.path())?;
-                            for cap in re.captures_iter(&content) {
-                                // Try the three capture groups in order.
-                                let query_candidate = cap.get(1)
-                                    .or_else(|| cap.get(2))
-                                    .or_else(|| cap.get(3));
-                                if let Some(m) = query_candidate {
-                                    let query = m.as_str().trim();
-                                    if query.is_empty() {
-                                        continue;
-                                    }
-                                    // Analyze the query string.
-                                    self.analyze_query(query, None)?;
+                    if let Some("ts" | "js" | "jsx" | "tsx" | "svelte" | "vue") = ext.to_str() {
+                        let content = fs::read_to_string(entry.path())?;
+                        for cap in re.captures_iter(&content) {
+                            // Try the three capture groups in order.
+                            let query_candidate = cap.get(1)
+                                .or_else(|| cap.get(2))
+                                .or_else(|| cap.get(3));
+                            if let Some(m) = query_candidate {
+                                let query = m.as_str().trim();
+                                if query.is_empty() {
+                                    continue;
                                 }
+                                // Analyze the query string.
+                                self.analyze_query(query, None)?;
                             }
                         }
-                        _ => {}
                     }
                 }
             }
@@ -156,20 +189,14 @@ impl Generator {
     // If a name is provided (such as for file-based queries), it is converted to PascalCase.
     fn analyze_query(&mut self, query: &str, name: Option<String>) -> Result<()> {
         let mut ctx = self.ctx.clone();
+        // A query may pick its own access method with a `-- @access <name>` comment.
+        if let Some(access) = Self::access_directive(query) {
+            ctx.set_access(access)?;
+        }
         let kind = analyzer::analyze(&mut ctx, query)?;
         let type_def = self.generate_type(&kind);
 
-        let query_name = name.map(|n| {
-            n.split('_')
-             .map(|word| {
-                let mut chars = word.chars();
-                match chars.next() {
-                    None => String::new(),
-                    Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-                }
-             })
-             .collect::<String>()
-        });
+        let query_name = name.as_deref().map(Self::to_pascal_case);
 
         // Get inferred parameter types (if any).
         let variables_type = if !ctx.get_all_inferred_params().is_empty() {
@@ -185,7 +212,7 @@ impl Generator {
 
         let doc_comment = format!(
             "/**\n * ## Query results\n *\n * Kind:\n * ```\n * {}\n * ```\n */",
-            kind.to_string()
+            kind
         );
 
         let info = QueryInfo {
@@ -201,12 +228,41 @@ impl Generator {
         Ok(())
     }
 
+    // to_pascal_case converts a snake_case (or camelCase) name to PascalCase.
+    fn to_pascal_case(name: &str) -> String {
+        name.split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    None => String::new(),
+                    Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                }
+            })
+            .collect()
+    }
+
+    // access_directive finds the access method named by an `@access` comment, such as:
+    //   -- @access account
+    fn access_directive(query: &str) -> Option<&str> {
+        query.lines().find_map(|line| {
+            let line = line.trim_start();
+            let comment = ["--", "//", "#"]
+                .iter()
+                .find_map(|marker| line.strip_prefix(marker))?;
+            comment.trim().strip_prefix("@access")?.split_whitespace().next()
+        })
+    }
+
     // generate_type converts a Kind to its corresponding TypeScript type definition.
     fn generate_type(&self, kind: &Kind) -> String {
+        // Statements that respond with an error, such as those of a cancelled transaction.
+        if analyzer::is_error_result(kind) {
+            return "QueryError".to_string();
+        }
         match kind {
             Kind::Null => "null".to_string(),
             Kind::Bool => "boolean".to_string(),
-            Kind::Number => "number".to_string(),
+            Kind::Number | Kind::Int | Kind::Float | Kind::Decimal => "number".to_string(),
             Kind::String => "string".to_string(),
             Kind::Datetime => "Date".to_string(),
             Kind::Duration => "Duration".to_string(),
@@ -239,6 +295,7 @@ impl Generator {
                     "Geometry".to_string()
                 }
             }
+            Kind::Option(inner) => format!("{} | undefined", self.generate_type(inner)),
             Kind::Either(kinds) => {
                 let types: Vec<String> = kinds.iter().map(|k| self.generate_type(k)).collect();
                 types.join(" | ")
@@ -257,28 +314,61 @@ impl Generator {
                 let types: Vec<String> = items.iter().map(|item| self.generate_type(item)).collect();
                 format!("[{}]", types.join(", "))
             }
-            Literal::Object(fields) => {
-                let field_defs: Vec<String> = fields
-                    .iter()
-                    .map(|(name, kind)| {
-                        let value = self.generate_type(kind);
-                        if value.contains('\n') {
-                            format!("  {}: {}", name, value.replace('\n', "\n  "))
-                        } else {
-                            format!("  {}: {}", name, value)
-                        }
-                    })
-                    .collect();
-                if field_defs.is_empty() {
-                    "{}".to_string()
+            Literal::Object(fields) => self.generate_object(fields, false),
+            _ => "any".to_string(),
+        }
+    }
+
+    // generate_input_type converts the kind of the data written to a table. Fields
+    // that accept NONE may be left out, so they are optional keys (`key?: T`).
+    fn generate_input_type(&self, kind: &Kind) -> String {
+        match kind {
+            Kind::Literal(Literal::Object(fields)) => self.generate_object(fields, true),
+            _ => self.generate_type(kind),
+        }
+    }
+
+    // generate_object converts the fields of an object literal, marking those that
+    // accept NONE as optional keys when `input` is set.
+    fn generate_object(&self, fields: &BTreeMap<String, Kind>, input: bool) -> String {
+        let field_defs: Vec<String> = fields
+            .iter()
+            .map(|(name, kind)| {
+                let (optional, value) = match kind {
+                    Kind::Option(inner) if input => (true, self.generate_input_type(inner)),
+                    _ if input => (false, self.generate_input_type(kind)),
+                    _ => (false, self.generate_type(kind)),
+                };
+                // Fields nested in arrays keep their path as a name, such as `tags[*].name`.
+                let name = if Self::is_identifier(name) {
+                    name.clone()
+                } else {
+                    format!("\"{}\"", Self::escape_string_literal(name))
+                };
+                let key = if optional { format!("{}?", name) } else { name };
+                if value.contains('\n') {
+                    format!("  {}: {}", key, value.replace('\n', "\n  "))
                 } else {
-                    format!("{{\n{}\n}}", field_defs.join(",\n"))
+                    format!("  {}: {}", key, value)
                 }
-            }
-            _ => "any".to_string(),
+            })
+            .collect();
+        if field_defs.is_empty() {
+            "{}".to_string()
+        } else {
+            format!("{{\n{}\n}}", field_defs.join(",\n"))
         }
     }
 
+    // is_identifier tells whether an object key can be written without quotes.
+    fn is_identifier(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '$')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+    }
+
     // escape_string_literal escapes backticks and other important characters.
     fn escape_string_literal(s: &str) -> String {
         s.replace('\\', "\\\\")
@@ -288,12 +378,82 @@ impl Generator {
          .replace('\t', "\\t")
     }
 
+    // check_names makes sure no two exports share a name. Tables, query files and
+    // the helpers every output holds are all named in PascalCase, so a table
+    // `queries` or a query file `user_create.surql` could clash with another export.
+    fn check_names(&self) -> Result<()> {
+        let helpers = [
+            "RecordId",
+            "Surreal",
+            "QueryError",
+            "Queries",
+            "Variables",
+            "TypedSurreal",
+            "surql",
+        ];
+        let mut names: HashMap<String, String> = helpers
+            .into_iter()
+            .map(|name| (name.to_string(), "the generated helpers".to_string()))
+            .collect();
+        let mut claim = |name: String, origin: String| match names.get(&name) {
+            Some(existing) => Err(CodegenError::NameCollision(name, existing.clone(), origin)),
+            None => {
+                names.insert(name, origin);
+                Ok(())
+            }
+        };
+
+        for table in self.ctx.table_names() {
+            let name = Self::to_pascal_case(table);
+            let origin = format!("table {}", table);
+            claim(format!("{}Create", name), origin.clone())?;
+            claim(format!("{}Patch", name), origin.clone())?;
+            claim(name, origin)?;
+        }
+        for info in self.query_types.values() {
+            if let Some(name) = &info.name {
+                let origin = format!("query {}", name);
+                if info.variables_type.is_some() {
+                    claim(format!("{}Variables", name), origin.clone())?;
+                }
+                claim(name.clone(), origin)?;
+            }
+        }
+        Ok(())
+    }
+
     // generate_output writes out the unified TypeScript definitions.
     // In the Queries mapping, every query's result type is inlined.
     // For queries discovered from files (with a name) we also export constants.
     fn generate_output(&self, path: &Path, should_format: bool) -> Result<()> {
         let mut content = String::new();
         content.push_str("import { type RecordId, Surreal } from 'surrealdb';\n\n");
+        content.push_str("export type QueryError = { error: string };\n\n");
+
+        self.check_names()?;
+
+        // Export the record type of every table, along with the data that creates
+        // a record (fields with a DEFAULT or VALUE are optional) and the data that
+        // patches one (every field is optional, READONLY fields are left out).
+        for table in self.ctx.table_names() {
+            let name = Self::to_pascal_case(table);
+            content.push_str(&format!(
+                "export type {} = {};\n\n",
+                name,
+                self.generate_type(&self.ctx.build_full_table_type(table)?)
+            ));
+            let inputs = [
+                (format!("{}Create", name), self.ctx.build_create_type(table)?),
+                (format!("{}Patch", name), self.ctx.build_patch_type(table)?),
+            ];
+            for (name, kind) in inputs {
+                content.push_str(&format!(
+                    "export type {} = {};\n\n",
+                    name,
+                    self.generate_input_type(&kind)
+                ));
+            }
+        }
 
         // Generate a unified Queries type mapping SQL strings to their definitions.
         content.push_str("export type Queries = {\n");
@@ -1,55 +1,172 @@
-// // surrealguard/crates/codegen/tests/typescript.rs
-// use surrealguard_macros::kind;
-
-// #[test]
-// fn test_basic_types() {
-//     assert_eq!(TypeScriptGenerator::generate(&kind!("string")), "string");
-//     assert_eq!(TypeScriptGenerator::generate(&kind!("number")), "number");
-//     assert_eq!(TypeScriptGenerator::generate(&kind!("bool")), "boolean");
-//     assert_eq!(TypeScriptGenerator::generate(&kind!("datetime")), "Date");
-//     assert_eq!(
-//         TypeScriptGenerator::generate(&kind!("duration")),
-//         "Duration"
-//     );
-// }
-
-// #[test]
-// fn test_record_types() {
-//     assert_eq!(
-//         TypeScriptGenerator::generate(&kind!("record<user>")),
-//         "(RecordId<\"user\"> & { id: string })"
-//     );
-// }
-
-// #[test]
-// fn test_geometry_types() {
-//     assert_eq!(
-//         TypeScriptGenerator::generate(&kind!("geometry<point>")),
-//         "Point"
-//     );
-//     assert_eq!(
-//         TypeScriptGenerator::generate(&kind!("geometry<polygon>")),
-//         "Polygon"
-//     );
-// }
-
-// #[test]
-// fn test_complex_object() {
-//     let kind = kind!(
-//         r#"{
-//         user: record<user>,
-//         location: geometry<point>,
-//         created: duration,
-//         posts: array<record<post>>
-//     }"#
-//     );
-
-//     let expected = r#"{
-//   created: Duration,
-//   location: Point,
-//   posts: Array<(RecordId<"post"> & { id: string })>,
-//   user: (RecordId<"user"> & { id: string })
-// }"#;
-
-//     assert_eq!(TypeScriptGenerator::generate(&kind), expected);
-// }
+use std::fs;
+
+use surrealguard_codegen::{generate, CodegenError, Config, Result};
+
+/// Writes the schema and query files into a fresh directory, generates the
+/// TypeScript definitions for them and returns the output.
+fn generate_for(test: &str, schema: &str, queries: &[(&str, &str)]) -> Result<String> {
+    let dir = std::env::temp_dir().join(format!("surrealguard-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("queries"))?;
+
+    fs::write(dir.join("schema.surql"), schema)?;
+    for (name, query) in queries {
+        fs::write(dir.join("queries").join(format!("{}.surql", name)), query)?;
+    }
+
+    fs::write(
+        dir.join("surrealguard.toml"),
+        format!(
+            r#"
+            version = "1"
+            language = "typescript"
+
+            [schema]
+            path = "{0}/schema.surql"
+
+            [queries]
+            path = "{0}/queries"
+
+            [output]
+            path = "{0}/types.ts"
+            format = false
+            "#,
+            dir.display()
+        ),
+    )?;
+
+    let (config, _) = Config::find_and_load(&dir)?;
+    let output = generate(&config).and_then(|_| Ok(fs::read_to_string(&config.output.path)?));
+    fs::remove_dir_all(&dir)?;
+    output
+}
+
+/// Finds the definition of an exported type in the generated output.
+fn exported_type(output: &str, name: &str) -> String {
+    let start = format!("export type {} = ", name);
+    let position = output
+        .find(&start)
+        .unwrap_or_else(|| panic!("{} should be exported", name));
+    let definition = &output[position + start.len()..];
+    definition[..definition.find(";\n").expect("Type should end")].to_string()
+}
+
+#[test]
+fn record_types() {
+    let output = generate_for(
+        "record-types",
+        r#"
+        DEFINE TABLE user SCHEMAFULL;
+            DEFINE FIELD name ON user TYPE string;
+            DEFINE FIELD active ON user TYPE bool;
+            DEFINE FIELD joined ON user TYPE datetime;
+            DEFINE FIELD location ON user TYPE geometry<point>;
+            DEFINE FIELD friends ON user TYPE array<record<user>>;
+        "#,
+        &[],
+    )
+    .expect("Generation should succeed");
+
+    assert_eq!(
+        exported_type(&output, "User"),
+        r#"{
+  active: boolean,
+  friends: Array<(RecordId<"user"> & { id: string })>,
+  joined: Date,
+  location: Point,
+  name: string
+}"#
+    );
+}
+
+#[test]
+fn nested_input_types() {
+    let output = generate_for(
+        "nested-input-types",
+        r#"
+        DEFINE TABLE user SCHEMAFULL;
+            DEFINE FIELD name ON user TYPE string;
+            DEFINE FIELD address ON user TYPE object;
+            DEFINE FIELD address.city ON user TYPE string;
+            DEFINE FIELD address.zip ON user TYPE option<string>;
+        "#,
+        &[],
+    )
+    .expect("Generation should succeed");
+
+    assert_eq!(
+        exported_type(&output, "UserCreate"),
+        r#"{
+  address: {
+    city: string,
+    zip?: string
+  },
+  name: string
+}"#
+    );
+    assert_eq!(
+        exported_type(&output, "UserPatch"),
+        r#"{
+  address?: {
+    city?: string,
+    zip?: string
+  },
+  name?: string
+}"#
+    );
+}
+
+#[test]
+fn optional_input_keys() {
+    let output = generate_for(
+        "optional-input-keys",
+        r#"
+        DEFINE TABLE post SCHEMAFULL;
+            DEFINE FIELD title ON post TYPE string;
+            DEFINE FIELD subtitle ON post TYPE option<string>;
+            DEFINE FIELD views ON post TYPE int DEFAULT 0;
+            DEFINE FIELD created ON post TYPE datetime VALUE time::now() READONLY;
+        "#,
+        &[],
+    )
+    .expect("Generation should succeed");
+
+    let create = exported_type(&output, "PostCreate");
+    assert!(create.contains("\n  title: string"), "{}", create);
+    assert!(create.contains("\n  subtitle?: string"), "{}", create);
+    assert!(create.contains("\n  views?: number"), "{}", create);
+    assert!(create.contains("\n  created?: Date"), "{}", create);
+
+    // Every patched field is optional, and READONLY fields can't be patched.
+    let patch = exported_type(&output, "PostPatch");
+    assert!(patch.contains("\n  title?: string"), "{}", patch);
+    assert!(!patch.contains("created"), "{}", patch);
+}
+
+#[test]
+fn clashing_type_names() {
+    let schema = r#"
+        DEFINE TABLE user SCHEMAFULL;
+            DEFINE FIELD name ON user TYPE string;
+    "#;
+
+    let err = generate_for(
+        "clashing-type-names",
+        schema,
+        &[("user_create", "SELECT * FROM user;")],
+    )
+    .expect_err("Generation should fail");
+    assert!(matches!(
+        err,
+        CodegenError::NameCollision(name, _, origin)
+            if name == "UserCreate" && origin == "query UserCreate"
+    ));
+
+    let output = generate_for(
+        "distinct-type-names",
+        schema,
+        &[("all_users", "SELECT * FROM user;")],
+    )
+    .expect("Generation should succeed");
+    assert!(output.contains("export const AllUsers = `SELECT * FROM user;`;"));
+}
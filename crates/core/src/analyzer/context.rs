@@ -19,70 +19,501 @@
 //! let mut ctx = AnalyzerContext::new();
 //!
 //! // Add inferred parameter directly
-//! ctx.add_inferred_param("$user", Kind::String);
+//! ctx.add_inferred_param("user", Kind::String, "user.name")
+//!     .expect("A first use never conflicts");
 //!
 //! // Get inferred types
 //! let param_types = ctx.get_all_inferred_params();
 //! assert_eq!(param_types.len(), 1);
 //! ```
 use std::collections::BTreeMap;
-use surrealdb::sql::statements::{DefineFieldStatement, DefineTableStatement};
+use surrealdb::sql::index::Distance;
+use surrealdb::sql::statements::{
+    DefineFieldStatement, DefineFunctionStatement, DefineTableStatement,
+};
 use surrealdb::sql::{statements::DefineStatement, Geometry, Kind, Table, Value};
-use surrealdb::sql::{Idiom, Literal, Part, TableType};
+use surrealdb::sql::{Ident, Idiom, Literal, Number, Part, Permission, Permissions, TableType};
 
 use super::error::{AnalyzerError, AnalyzerResult};
+use super::expressions::analyze_expression;
+use super::functions::analyze_function;
+use super::idiom::resolve_idiom;
+use super::kind::{union_of, KindExt};
+use super::statements::{analyze_subquery, logic::analyze_block};
+
+/// The signature of a custom `fn::` function, as declared by `DEFINE FUNCTION`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionSignature {
+    /// Argument names (without the leading `$`) and their kinds.
+    pub args: Vec<(String, Kind)>,
+    /// The declared return kind, or the kind inferred from the function body.
+    pub returns: Kind,
+}
+
+/// An access method declared by `DEFINE ACCESS` (or a legacy `DEFINE SCOPE`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessMethod {
+    /// The table of the records signed in through this method, if it is a
+    /// record access method whose SIGNIN or SIGNUP query names one.
+    pub table: Option<String>,
+    /// Whether tokens are issued outside SurrealDB (`TYPE JWT`), in which case
+    /// the standard claims may be missing.
+    pub external: bool,
+    /// Custom claims carried by the access method's tokens.
+    pub claims: BTreeMap<String, Kind>,
+}
+
+/// Identifies a definition within the schema, the way DEFINE and REMOVE
+/// statements name it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DefinitionKey {
+    Table(String),
+    /// A field, by table and field path.
+    Field(String, String),
+    /// An index, by table and index name.
+    Index(String, String),
+    /// An event, by table and event name.
+    Event(String, String),
+    /// A custom function, by name without the `fn::` prefix.
+    Function(String),
+    Param(String),
+    Analyzer(String),
+    Access(String),
+}
+
+impl DefinitionKey {
+    /// The key of what `definition` defines, if it is part of the modeled schema.
+    pub fn of(definition: &DefineStatement) -> Option<Self> {
+        Some(match definition {
+            DefineStatement::Table(def) => Self::Table(def.name.to_raw()),
+            DefineStatement::Field(def) => Self::Field(def.what.to_raw(), field_path(&def.name)),
+            DefineStatement::Index(def) => Self::Index(def.what.to_raw(), def.name.to_raw()),
+            DefineStatement::Event(def) => Self::Event(def.what.to_raw(), def.name.to_raw()),
+            DefineStatement::Function(def) => Self::Function(def.name.to_raw()),
+            DefineStatement::Param(def) => Self::Param(def.name.to_raw()),
+            DefineStatement::Analyzer(def) => Self::Analyzer(def.name.to_raw()),
+            DefineStatement::Access(def) => Self::Access(def.name.to_raw()),
+            _ => return None,
+        })
+    }
+}
+
+impl std::fmt::Display for DefinitionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Table(name) => write!(f, "table '{}'", name),
+            Self::Field(table, name) => write!(f, "field '{}' on table '{}'", name, table),
+            Self::Index(table, name) => write!(f, "index '{}' on table '{}'", name, table),
+            Self::Event(table, name) => write!(f, "event '{}' on table '{}'", name, table),
+            Self::Function(name) => write!(f, "function 'fn::{}'", name),
+            Self::Param(name) => write!(f, "param '${}'", name),
+            Self::Analyzer(name) => write!(f, "analyzer '{}'", name),
+            Self::Access(name) => write!(f, "access method '{}'", name),
+        }
+    }
+}
+
+/// A full-text analyzer declared by `DEFINE ANALYZER`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextAnalyzer {
+    /// The function applied to the text first, without the `fn::` prefix.
+    pub function: Option<String>,
+    /// The tokenizers splitting the text, such as `BLANK` or `CLASS`.
+    pub tokenizers: Vec<String>,
+    /// The filters applied to each token, such as `LOWERCASE` or `EDGENGRAM(2,10)`.
+    pub filters: Vec<String>,
+}
+
+/// Analyzers SurrealDB provides without a DEFINE ANALYZER, used by SEARCH
+/// indexes that don't name one.
+const BUILTIN_ANALYZERS: &[&str] = &["like"];
+
+/// An index declared by `DEFINE INDEX`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableIndex {
+    pub name: String,
+    pub table: String,
+    /// The indexed fields, in order.
+    pub fields: Vec<Idiom>,
+    pub kind: IndexKind,
+}
+
+/// The kind of an index, and the operators it serves.
+#[derive(Clone, Debug, PartialEq)]
+pub enum IndexKind {
+    Plain,
+    Unique,
+    /// A full-text index, searched with `@@`.
+    Search {
+        analyzer: String,
+        highlights: bool,
+    },
+    /// A vector index, searched with `<|k|>`.
+    MTree {
+        dimension: u16,
+        distance: Distance,
+    },
+    /// An approximate vector index, searched with `<|k,ef|>`.
+    Hnsw {
+        dimension: u16,
+        distance: Distance,
+    },
+}
+
+/// The role of a system (root, namespace or database) user.
+///
+/// System users aren't subject to PERMISSIONS clauses, but viewers can only read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Viewer,
+    Editor,
+    Owner,
+}
+
+/// What a statement does with a table or field, as checked by PERMISSIONS clauses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Select,
+    Create,
+    Update,
+    Delete,
+}
+
+/// Whether queries may perform an action at the current access level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Grant {
+    /// Always allowed (`FULL`).
+    Full,
+    /// Allowed for some records only (`WHERE ..`).
+    Conditional,
+    /// Never allowed (`NONE`).
+    Denied,
+}
+
+/// Parameters SurrealDB provides to every query, which the client never supplies.
+const SYSTEM_PARAMS: &[&str] = &["auth", "token", "session", "access"];
 
 #[derive(Clone)]
 pub struct AnalyzerContext {
     definitions: Vec<DefineStatement>,
+    /// Signatures of custom functions, keyed by name without the `fn::` prefix.
+    functions: BTreeMap<String, FunctionSignature>,
     /// Parameters whose types are inferred based on usage or positioning.
     ///
     /// In certain contexts, particularly UPDATE or CREATE,
     /// It is possible to infer the required type of a parameter.
     /// This has to be bubbled up to the codegen for processing.
     inferred_params: Vec<(String, Kind)>,
-    /// Table name for the current scope user.
-    auth: Option<String>,
+    /// Where each inferred parameter's kind was inferred from, for diagnostics.
+    param_sites: BTreeMap<String, String>,
+    /// Function calls being analyzed, innermost last.
+    calls: Vec<String>,
+    /// Parameters bound by the enclosing statements, innermost scope last.
+    ///
+    /// `$this` is bound to the record being processed while a statement
+    /// resolves its projections and conditions.
+    scopes: Vec<BTreeMap<String, Kind>>,
+    /// Database parameters declared by `DEFINE PARAM`, available to every query.
+    defined_params: BTreeMap<String, Kind>,
+    /// Access methods declared by `DEFINE ACCESS`, keyed by name.
+    access_methods: BTreeMap<String, AccessMethod>,
+    /// The access method queries are analyzed under, which determines `$auth`.
+    access: Option<String>,
+    /// The role of the system user queries are analyzed as, if any.
+    role: Option<Role>,
+    /// PERMISSIONS clauses of tables (keyed by table) and fields (keyed by
+    /// `table.field`). They apply to record users only.
+    permissions: BTreeMap<String, Permissions>,
+    /// Full-text analyzers declared by `DEFINE ANALYZER`, keyed by name.
+    analyzers: BTreeMap<String, TextAnalyzer>,
+    /// Indexes declared by `DEFINE INDEX`.
+    indexes: Vec<TableIndex>,
+    /// Fields of the views declared by `DEFINE TABLE ... AS SELECT`, derived
+    /// from their projections and keyed by table.
+    views: BTreeMap<String, Vec<DefineFieldStatement>>,
+    /// Indexes searched by the `@N@` operators of the statement being
+    /// analyzed, keyed by `N`, for `search::score(N)` and the like.
+    match_refs: BTreeMap<u8, TableIndex>,
+}
 
-    // A list of identifiers and their corresponding
-    // justifications for alterations to the original 'Kind'.
-    permissions: BTreeMap<String, String>,
+impl Default for AnalyzerContext {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AnalyzerContext {
     pub fn new() -> Self {
         Self {
             definitions: Vec::new(),
+            functions: BTreeMap::new(),
             inferred_params: Vec::new(),
-            auth: None,
+            param_sites: BTreeMap::new(),
+            calls: Vec::new(),
+            scopes: Vec::new(),
+            defined_params: BTreeMap::new(),
+            access_methods: BTreeMap::new(),
+            access: None,
+            role: None,
             permissions: BTreeMap::new(),
+            analyzers: BTreeMap::new(),
+            indexes: Vec::new(),
+            views: BTreeMap::new(),
+            match_refs: BTreeMap::new(),
         }
     }
 
+    /// The table of the record `$auth` refers to under the active access method.
     pub fn auth(&self) -> Option<&str> {
-        self.auth.as_deref()
+        self.access_method()
+            .and_then(|method| method.table.as_deref())
     }
 
-    /// Registers a permission for a field path.
-    ///
-    /// This function allows you to associate a permission with a specific field path.
-    /// The field path is a string representing the path to the field, and the permission
-    /// is a string representing the required permission for accessing the field.
+    /// Resolves the kind of `$auth`. Without an access method, as in a schema's
+    /// PERMISSIONS clauses, it may be anything; under an access method that isn't a
+    /// record method it fails.
+    pub fn auth_kind(&self) -> AnalyzerResult<Kind> {
+        match (self.auth(), &self.access) {
+            (Some(table), _) => Ok(Kind::Record(vec![Table::from(table)])),
+            (None, None) => Ok(Kind::Any),
+            (None, Some(_)) => Err(AnalyzerError::MissingAuth),
+        }
+    }
+
+    /// Registers an access method declared by `DEFINE ACCESS`.
+    pub fn register_access(&mut self, name: &str, method: AccessMethod) {
+        self.access_methods.insert(name.to_string(), method);
+    }
+
+    /// Declares custom claims carried by the tokens of an access method.
+    pub fn register_claims(
+        &mut self,
+        access: &str,
+        claims: impl IntoIterator<Item = (String, Kind)>,
+    ) -> AnalyzerResult<()> {
+        let method = self
+            .access_methods
+            .get_mut(access)
+            .ok_or_else(|| AnalyzerError::AccessNotFound(access.to_string()))?;
+        method.claims.extend(claims);
+        Ok(())
+    }
+
+    /// Selects the access method that queries are analyzed under, as a record user.
+    pub fn set_access(&mut self, name: &str) -> AnalyzerResult<()> {
+        if !self.access_methods.contains_key(name) {
+            return Err(AnalyzerError::AccessNotFound(name.to_string()));
+        }
+        self.access = Some(name.to_string());
+        self.role = None;
+        Ok(())
+    }
+
+    /// Analyzes queries as a system user with the given role.
+    pub fn set_role(&mut self, role: Role) {
+        self.role = Some(role);
+        self.access = None;
+    }
+
+    /// Resolves the kind of `$token`: the standard claims of the active access
+    /// method's tokens, followed by its custom claims.
+    pub fn token_kind(&self) -> AnalyzerResult<Kind> {
+        let method = self
+            .access_method()
+            .ok_or_else(|| AnalyzerError::MissingAccess("token".to_string()))?;
+
+        let mut claims = BTreeMap::new();
+        for claim in ["exp", "iat", "nbf"] {
+            claims.insert(claim.to_string(), Kind::Int);
+        }
+        for claim in ["iss", "jti", "NS", "DB", "AC"] {
+            claims.insert(claim.to_string(), Kind::String);
+        }
+        if let Some(table) = &method.table {
+            claims.insert(
+                "ID".to_string(),
+                Kind::Record(vec![Table::from(table.as_str())]),
+            );
+        }
+        if method.external {
+            for kind in claims.values_mut() {
+                *kind = Kind::Option(Box::new(kind.clone()));
+            }
+        }
+
+        claims.extend(method.claims.clone());
+        Ok(Kind::Literal(Literal::Object(claims)))
+    }
+
+    /// The active access method, if one was selected.
+    pub fn access_method(&self) -> Option<&AccessMethod> {
+        self.access
+            .as_ref()
+            .and_then(|name| self.access_methods.get(name))
+    }
+
+    /// Registers the PERMISSIONS clause of a table (`user`) or a field (`user.email`).
     ///
     /// Example usage:
     ///
     /// ```
     /// use surrealguard_core::prelude::AnalyzerContext;
+    /// use surrealdb::sql::Permissions;
     /// let mut context = AnalyzerContext::new();
-    /// context.register_permission("users.email", "read");
+    /// context.register_permission("user.email", Permissions::none());
     /// ```
-    pub fn register_permission(&mut self, field_path: &str, permission: &str) {
-        self.permissions
-            .insert(field_path.to_string(), permission.to_string());
+    pub fn register_permission(&mut self, path: &str, permissions: Permissions) {
+        self.permissions.insert(path.to_string(), permissions);
     }
 
-    pub fn add_inferred_param(&mut self, name: &str, kind: Kind) {
-        self.inferred_params.push((name.to_string(), kind));
+    /// Whether queries may perform `action` on the records of `table`.
+    ///
+    /// Root queries and system users (other than viewers writing) may do
+    /// anything. Record users are limited by the table's PERMISSIONS clause, and
+    /// may do nothing with tables that aren't defined.
+    pub fn table_grant(&self, table: &str, action: Action) -> Grant {
+        match self.permissions.get(table) {
+            Some(permissions) => self.grant(permissions, action),
+            None => self.grant(&Permissions::none(), action),
+        }
+    }
+
+    /// Whether queries may perform `action` on `field` of `table`, given the
+    /// PERMISSIONS clauses of the field and of the fields containing it.
+    pub fn field_grant(&self, table: &str, field: &Idiom, action: Action) -> Grant {
+        (1..=field.0.len())
+            .filter_map(|len| {
+                let path = format!("{}.{}", table, field_path(&field.0[..len]));
+                self.permissions.get(&path)
+            })
+            .map(|permissions| self.grant(permissions, action))
+            .fold(Grant::Full, |grant, other| match (grant, other) {
+                (Grant::Denied, _) | (_, Grant::Denied) => Grant::Denied,
+                (Grant::Conditional, _) | (_, Grant::Conditional) => Grant::Conditional,
+                _ => Grant::Full,
+            })
+    }
+
+    /// Applies a PERMISSIONS clause at the current access level.
+    fn grant(&self, permissions: &Permissions, action: Action) -> Grant {
+        match (self.role, &self.access) {
+            (Some(Role::Viewer), _) if action != Action::Select => Grant::Denied,
+            (Some(_), _) | (None, None) => Grant::Full,
+            (None, Some(_)) => {
+                let permission = match action {
+                    Action::Select => &permissions.select,
+                    Action::Create => &permissions.create,
+                    Action::Update => &permissions.update,
+                    Action::Delete => &permissions.delete,
+                };
+                match permission {
+                    Permission::Full => Grant::Full,
+                    Permission::Specific(_) => Grant::Conditional,
+                    Permission::None => Grant::Denied,
+                    _ => Grant::Conditional,
+                }
+            }
+        }
+    }
+
+    /// The kind of a field as queries at the current access level read it.
+    ///
+    /// Fields that can't be selected are never returned, and fields selectable
+    /// for some records only may be missing.
+    pub fn visible_field_kind(&self, field_def: &DefineFieldStatement) -> Option<Kind> {
+        let kind = field_def.kind.clone()?;
+        match self.field_grant(&field_def.what, &field_def.name, Action::Select) {
+            Grant::Full => Some(kind),
+            Grant::Conditional => Some(union_of([kind, Kind::Null])),
+            Grant::Denied => None,
+        }
+    }
+
+    /// Records that the input parameter `name` is used as `kind` at `site`.
+    ///
+    /// A parameter used in several places is unified: the stricter of two
+    /// compatible kinds is kept (`string` over `option<string>`), while
+    /// incompatible kinds are reported with both sites.
+    pub fn add_inferred_param(&mut self, name: &str, kind: Kind, site: &str) -> AnalyzerResult<()> {
+        // Parameters bound within the query (e.g. by LET), defined in the
+        // database (by DEFINE PARAM) or provided by SurrealDB are not external inputs.
+        if self.get_param(name).is_some() || SYSTEM_PARAMS.contains(&name) {
+            return Ok(());
+        }
+
+        let Some(index) = self
+            .inferred_params
+            .iter()
+            .position(|(param_name, _)| param_name == name)
+        else {
+            self.inferred_params.push((name.to_string(), kind));
+            self.param_sites.insert(name.to_string(), site.to_string());
+            return Ok(());
+        };
+
+        let existing = &self.inferred_params[index].1;
+        if existing.is_assignable_from(&kind) {
+            if *existing != kind {
+                self.inferred_params[index].1 = kind;
+                self.param_sites.insert(name.to_string(), site.to_string());
+            }
+            Ok(())
+        } else if kind.is_assignable_from(existing) {
+            Ok(())
+        } else {
+            Err(AnalyzerError::ParameterConflict {
+                param: name.to_string(),
+                first: existing.to_string(),
+                first_site: self.param_sites.get(name).cloned().unwrap_or_default(),
+                second: kind.to_string(),
+                second_site: site.to_string(),
+            })
+        }
+    }
+
+    /// Returns true if `value` is a parameter the client supplies: it is
+    /// neither bound within the query nor provided by the database.
+    pub fn is_input_param(&self, value: &Value) -> bool {
+        match value {
+            Value::Param(param) => {
+                self.get_param(param).is_none() && !SYSTEM_PARAMS.contains(&param.as_str())
+            }
+            _ => false,
+        }
+    }
+
+    /// Infers `value` as `kind` at `site` if it is an input parameter,
+    /// returning the parameter's unified kind.
+    pub fn infer_param(
+        &mut self,
+        value: &Value,
+        kind: &Kind,
+        site: &str,
+    ) -> AnalyzerResult<Option<Kind>> {
+        let Value::Param(param) = value else {
+            return Ok(None);
+        };
+        if !self.is_input_param(value) {
+            return Ok(None);
+        }
+        self.add_inferred_param(param, kind.clone(), site)?;
+        Ok(self.get_inferred_param(param).cloned())
+    }
+
+    /// Marks the start of the analysis of a function call, so parameters
+    /// passed as its arguments can be traced back to it.
+    pub fn enter_call(&mut self, call: String) {
+        self.calls.push(call);
+    }
+
+    /// Marks the end of the analysis of the innermost function call.
+    pub fn exit_call(&mut self) {
+        self.calls.pop();
+    }
+
+    /// The innermost function call being analyzed.
+    pub fn current_call(&self) -> Option<&str> {
+        self.calls.last().map(String::as_str)
     }
 
     pub fn get_inferred_param(&self, name: &str) -> Option<&Kind> {
@@ -96,16 +527,60 @@ impl AnalyzerContext {
         &self.inferred_params
     }
 
+    /// Opens a new scope for parameter bindings.
+    pub fn push_scope(&mut self) {
+        self.scopes.push(BTreeMap::new());
+    }
+
+    /// Closes the innermost scope, dropping its bindings.
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Binds a parameter (without the leading `$`) in the innermost scope.
+    pub fn bind_param(&mut self, name: &str, kind: Kind) {
+        if self.scopes.is_empty() {
+            self.push_scope();
+        }
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), kind);
+        }
+    }
+
+    /// Registers a database parameter (without the leading `$`) declared by `DEFINE PARAM`.
+    pub fn define_param(&mut self, name: &str, kind: Kind) {
+        self.defined_params.insert(name.to_string(), kind);
+    }
+
+    /// Looks up a bound parameter, starting from the innermost scope and
+    /// falling back to the database parameters.
+    pub fn get_param(&self, name: &str) -> Option<&Kind> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .or_else(|| self.defined_params.get(name))
+    }
+
+    /// Resolves `value` with `$this` bound to `document`, the way projections
+    /// and conditions see the record they are evaluated on.
+    pub fn resolve_in_document(&mut self, document: &Kind, value: &Value) -> AnalyzerResult<Kind> {
+        self.push_scope();
+        self.bind_param("this", document.clone());
+        let kind = self.resolve(value);
+        self.pop_scope();
+        kind
+    }
+
     pub fn infer_param_from_field(
         &mut self,
         table: &str,
         field: &Idiom,
         param: &str,
     ) -> AnalyzerResult<()> {
-        if let Some(DefineStatement::Field(field_def)) = self.find_field_definition(table, field) {
+        if let Some(field_def) = self.find_field_definition(table, field) {
             if let Some(kind) = field_def.kind.clone() {
-                self.add_inferred_param(param, kind);
-                Ok(())
+                self.add_inferred_param(param, kind, &format!("{}.{}", table, field))
             } else {
                 Err(AnalyzerError::schema_violation(
                     "Field type not defined",
@@ -120,8 +595,7 @@ impl AnalyzerContext {
 
     pub fn infer_param_from_table(&mut self, table: &str, param: &str) -> AnalyzerResult<()> {
         let table_type = self.build_full_table_type(table)?;
-        self.add_inferred_param(param, table_type);
-        Ok(())
+        self.add_inferred_param(param, table_type, table)
     }
 
     /// Gets the target table of a relation
@@ -148,16 +622,66 @@ impl AnalyzerContext {
         }
     }
 
+    /// Builds the kind of the records of a table, as queries return them.
     pub fn build_full_table_type(&self, table_name: &str) -> AnalyzerResult<Kind> {
         let mut field_types = BTreeMap::new();
         for field_def in self.get_field_definitions(table_name) {
+            if let Some(kind) = self.visible_field_kind(field_def) {
+                insert_field(&mut field_types, &field_def.name.0, kind);
+            }
+        }
+        Ok(Kind::Literal(Literal::Object(field_types)))
+    }
+
+    /// Builds the kind of the data that creates (or replaces) a record of a table.
+    ///
+    /// Fields the database fills in itself, through a DEFAULT or a VALUE
+    /// clause (including `<future>` values), are optional.
+    pub fn build_create_type(&self, table_name: &str) -> AnalyzerResult<Kind> {
+        let mut field_types = BTreeMap::new();
+        for field_def in self.get_field_definitions(table_name) {
+            if let Some(kind) = field_def.kind.clone() {
+                let kind = match field_def.default.is_some() || field_def.value.is_some() {
+                    true => union_of([kind, Kind::Null]),
+                    false => kind,
+                };
+                insert_field(&mut field_types, &field_def.name.0, kind);
+            }
+        }
+        Ok(Kind::Literal(Literal::Object(field_types)))
+    }
+
+    /// Builds the kind of the data merged into an existing record of a table.
+    ///
+    /// Every field is optional, and READONLY fields can't be changed at all.
+    pub fn build_patch_type(&self, table_name: &str) -> AnalyzerResult<Kind> {
+        let mut field_types = BTreeMap::new();
+        for field_def in self.get_field_definitions(table_name) {
+            if field_def.readonly {
+                continue;
+            }
             if let Some(kind) = field_def.kind.clone() {
-                field_types.insert(field_def.name.to_string(), kind);
+                insert_field(
+                    &mut field_types,
+                    &field_def.name.0,
+                    union_of([kind, Kind::Null]),
+                );
             }
         }
         Ok(Kind::Literal(Literal::Object(field_types)))
     }
 
+    /// Names of the defined tables, in definition order.
+    pub fn table_names(&self) -> Vec<&str> {
+        self.definitions
+            .iter()
+            .filter_map(|def| match def {
+                DefineStatement::Table(table_def) => Some(table_def.name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Finds a relation definition (i.e. a table whose TableType is Relation)
     /// matching the given relation idiom.
     pub fn find_relation_definition(
@@ -194,6 +718,8 @@ impl AnalyzerContext {
         })
     }
 
+    /// Gets the fields of a table, including those a view derives from its
+    /// projection.
     pub fn get_field_definitions(&self, table_name: &str) -> Vec<&DefineFieldStatement> {
         self.definitions
             .iter()
@@ -208,50 +734,271 @@ impl AnalyzerContext {
                     None
                 }
             })
+            .chain(self.views.get(table_name).into_iter().flatten())
             .collect()
     }
 
+    /// Registers a table as a view whose records have the given fields.
+    ///
+    /// The fields are READONLY, as the database computes them itself.
+    pub fn register_view(&mut self, table: &str, fields: BTreeMap<String, Kind>) {
+        let fields = fields
+            .into_iter()
+            .map(|(name, kind)| {
+                let mut field = DefineFieldStatement::default();
+                field.name = Idiom::from(
+                    name.split('.')
+                        .map(|part| Part::from(part.to_string()))
+                        .collect::<Vec<_>>(),
+                );
+                field.what = Ident::from(table.to_string());
+                field.kind = Some(kind);
+                field.readonly = true;
+                field
+            })
+            .collect();
+        self.views.insert(table.to_string(), fields);
+    }
+
+    /// Forgets that a table is a view, returning true if it was one.
+    pub fn remove_view(&mut self, table: &str) -> bool {
+        self.views.remove(table).is_some()
+    }
+
+    /// Returns true if the table is a view, whose records can't be written.
+    pub fn is_view(&self, table: &str) -> bool {
+        self.views.contains_key(table)
+    }
+
+    /// Finds the definition of a field, or of the closest field containing it,
+    /// among the fields [`get_field_definitions`](Self::get_field_definitions)
+    /// gives for the table.
     pub fn find_field_definition(
         &self,
         table_name: &str,
         field_idiom: &Idiom,
-    ) -> Option<&DefineStatement> {
-        // Try exact match first
-        let exact_match = self.definitions.iter().find(|def| {
-            if let DefineStatement::Field(field_def) = def {
-                field_def.what.0 == table_name && &field_def.name == field_idiom
-            } else {
-                false
-            }
-        });
+    ) -> Option<&DefineFieldStatement> {
+        let fields = self.get_field_definitions(table_name);
+        (1..=field_idiom.0.len())
+            .rev()
+            .find_map(|len| {
+                fields
+                    .iter()
+                    .find(|field_def| field_def.name.0 == field_idiom.0[..len])
+            })
+            .copied()
+    }
 
-        if exact_match.is_some() {
-            return exact_match;
-        }
+    /// Finds the definition of a custom function by its name, without the `fn::` prefix.
+    pub fn find_function_definition(&self, name: &str) -> Option<&DefineFunctionStatement> {
+        self.definitions.iter().find_map(|def| match def {
+            DefineStatement::Function(func_def) if func_def.name.0 == name => Some(func_def),
+            _ => None,
+        })
+    }
 
-        // If no exact match, try parent paths
-        if field_idiom.0.len() > 1 {
-            let parent_parts: Vec<Part> = field_idiom.0[..field_idiom.0.len() - 1]
-                .iter()
-                .map(|p| Part::from(p.to_string()))
-                .collect();
+    /// Registers the signature of a custom function, by name without the `fn::` prefix.
+    pub fn register_function(&mut self, name: &str, signature: FunctionSignature) {
+        self.functions.insert(name.to_string(), signature);
+    }
+
+    /// Gets the signature of a custom function, by name without the `fn::` prefix.
+    pub fn get_function(&self, name: &str) -> Option<&FunctionSignature> {
+        self.functions.get(name)
+    }
 
-            let parent_idiom = Idiom::from(parent_parts);
-            self.find_field_definition(table_name, &parent_idiom)
+    /// Registers a full-text analyzer declared by `DEFINE ANALYZER`.
+    pub fn register_analyzer(&mut self, name: &str, analyzer: TextAnalyzer) {
+        self.analyzers.insert(name.to_string(), analyzer);
+    }
+
+    /// Gets a full-text analyzer declared by `DEFINE ANALYZER`.
+    pub fn get_analyzer(&self, name: &str) -> Option<&TextAnalyzer> {
+        self.analyzers.get(name)
+    }
+
+    /// Checks that a full-text analyzer exists, being declared or built in.
+    pub fn check_analyzer(&self, name: &str) -> AnalyzerResult<()> {
+        if self.analyzers.contains_key(name) || BUILTIN_ANALYZERS.contains(&name) {
+            Ok(())
         } else {
-            None
+            Err(AnalyzerError::AnalyzerNotFound(name.to_string()))
         }
     }
 
+    /// Registers an index declared by `DEFINE INDEX`, replacing any index of the
+    /// same name on the same table.
+    pub fn register_index(&mut self, index: TableIndex) {
+        self.indexes
+            .retain(|other| other.table != index.table || other.name != index.name);
+        self.indexes.push(index);
+    }
+
+    /// Gets the indexes of a table.
+    pub fn get_indexes(&self, table: &str) -> Vec<&TableIndex> {
+        self.indexes
+            .iter()
+            .filter(|index| index.table == table)
+            .collect()
+    }
+
+    /// Records that the `@N@` operator numbered `reference` searches `index`.
+    pub fn register_match_ref(&mut self, reference: u8, index: TableIndex) {
+        self.match_refs.insert(reference, index);
+    }
+
+    /// Gets the index searched by the `@N@` operator numbered `reference`.
+    pub fn get_match_ref(&self, reference: u8) -> Option<&TableIndex> {
+        self.match_refs.get(&reference)
+    }
+
+    /// Clears the `@N@` references before a statement is analyzed, returning
+    /// those of the enclosing statement.
+    pub fn take_match_refs(&mut self) -> BTreeMap<u8, TableIndex> {
+        std::mem::take(&mut self.match_refs)
+    }
+
+    /// Restores the `@N@` references of the enclosing statement.
+    pub fn restore_match_refs(&mut self, match_refs: BTreeMap<u8, TableIndex>) {
+        self.match_refs = match_refs;
+    }
+
+    /// Records a definition, replacing the previous definition of the same
+    /// thing in place.
     pub fn append_definition(&mut self, definition: DefineStatement) {
-        self.definitions.push(definition);
+        let key = DefinitionKey::of(&definition);
+        let existing = key.and_then(|key| {
+            self.definitions
+                .iter()
+                .position(|def| DefinitionKey::of(def).as_ref() == Some(&key))
+        });
+        match existing {
+            Some(index) => self.definitions[index] = definition,
+            None => self.definitions.push(definition),
+        }
+    }
+
+    /// Returns true if the schema defines what `key` identifies.
+    pub fn is_defined(&self, key: &DefinitionKey) -> bool {
+        self.definitions
+            .iter()
+            .any(|def| DefinitionKey::of(def).as_ref() == Some(key))
+    }
+
+    /// Removes what `key` identifies from the schema, returning false if it
+    /// wasn't defined. Removing a table removes its fields, indexes and events.
+    pub fn remove_definition(&mut self, key: &DefinitionKey) -> bool {
+        if !self.is_defined(key) {
+            return false;
+        }
+
+        self.definitions
+            .retain(|def| match (DefinitionKey::of(def), key) {
+                (
+                    Some(
+                        DefinitionKey::Field(table, _)
+                        | DefinitionKey::Index(table, _)
+                        | DefinitionKey::Event(table, _),
+                    ),
+                    DefinitionKey::Table(removed),
+                ) => table != *removed,
+                (def_key, key) => def_key.as_ref() != Some(key),
+            });
+
+        match key {
+            DefinitionKey::Table(table) => {
+                let fields = format!("{}.", table);
+                self.permissions
+                    .retain(|path, _| path != table && !path.starts_with(&fields));
+                self.indexes.retain(|index| index.table != *table);
+                self.remove_view(table);
+            }
+            DefinitionKey::Field(table, field) => {
+                self.permissions.remove(&format!("{}.{}", table, field));
+            }
+            DefinitionKey::Index(table, name) => {
+                self.indexes
+                    .retain(|index| index.table != *table || index.name != *name);
+            }
+            DefinitionKey::Event(..) => {}
+            DefinitionKey::Function(name) => {
+                self.functions.remove(name);
+            }
+            DefinitionKey::Param(name) => {
+                self.defined_params.remove(name);
+            }
+            DefinitionKey::Analyzer(name) => {
+                self.analyzers.remove(name);
+            }
+            DefinitionKey::Access(name) => {
+                self.access_methods.remove(name);
+                if self.access.as_ref() == Some(name) {
+                    self.access = None;
+                }
+            }
+        }
+        true
+    }
+
+    /// Gets the definition of a table, to change it in place.
+    pub fn find_table_definition_mut(
+        &mut self,
+        table_name: &str,
+    ) -> Option<&mut DefineTableStatement> {
+        self.definitions.iter_mut().find_map(|def| match def {
+            DefineStatement::Table(table_def) if table_def.name.0 == table_name => Some(table_def),
+            _ => None,
+        })
     }
-    pub fn resolve(&self, value: &Value) -> AnalyzerResult<Kind> {
+
+    /// Resolves a value in a position that expects a value of kind `expected`.
+    ///
+    /// Values of unknown kind, and values whose kind is compatible with the
+    /// expectation (for example an `int` field where a `number` is expected),
+    /// resolve to the expected kind so callers can match on the shape they asked
+    /// for. Generic array expectations keep the element kind of the value.
+    /// Incompatible values resolve to their own kind, unchanged.
+    pub fn resolve_as(&mut self, value: &Value, expected: &Kind) -> AnalyzerResult<Kind> {
+        // A parameter passed where a kind is expected takes that kind.
+        if *expected != Kind::Any {
+            let site = self
+                .current_call()
+                .map_or_else(|| value.to_string(), str::to_string);
+            if let Some(kind) = self.infer_param(value, expected, &site)? {
+                return Ok(kind);
+            }
+        }
+        let found = self.resolve(value)?;
+        if found == Kind::Any {
+            return Ok(expected.clone());
+        }
+        if !expected.is_assignable_from(&found) {
+            return Ok(found);
+        }
+        Ok(match (expected, found) {
+            (Kind::Array(inner, _), Kind::Array(found_inner, len)) if **inner == Kind::Any => {
+                Kind::Array(found_inner, len)
+            }
+            (Kind::Array(inner, _), Kind::Literal(Literal::Array(items)))
+                if **inner == Kind::Any =>
+            {
+                Kind::Array(Box::new(union_of(items)), None)
+            }
+            _ => expected.clone(),
+        })
+    }
+
+    pub fn resolve(&mut self, value: &Value) -> AnalyzerResult<Kind> {
         Ok(match value {
             Value::None => Kind::Null,
             Value::Null => Kind::Null,
             Value::Bool(_) => Kind::Bool,
-            Value::Number(_) => Kind::Number,
+            Value::Number(number) => match number {
+                Number::Int(_) => Kind::Int,
+                Number::Float(_) => Kind::Float,
+                Number::Decimal(_) => Kind::Decimal,
+                _ => Kind::Number,
+            },
             Value::Strand(_) => Kind::String,
             Value::Duration(_) => Kind::Duration,
             Value::Datetime(_) => Kind::Datetime,
@@ -260,10 +1007,20 @@ impl AnalyzerContext {
                 if array.is_empty() {
                     Kind::Array(Box::new(Kind::Any), None)
                 } else {
-                    Kind::Array(Box::new(self.resolve(&array[0])?), None)
+                    let items = array
+                        .iter()
+                        .map(|item| self.resolve(item))
+                        .collect::<AnalyzerResult<Vec<_>>>()?;
+                    Kind::Array(Box::new(union_of(items)), None)
                 }
             }
-            Value::Object(_) => Kind::Object,
+            Value::Object(object) => {
+                let mut fields = BTreeMap::new();
+                for (key, value) in object.iter() {
+                    fields.insert(key.clone(), self.resolve(value)?);
+                }
+                Kind::Literal(Literal::Object(fields))
+            }
             Value::Geometry(geometry) => match geometry {
                 Geometry::Point(_) => Kind::Geometry(vec!["point".to_string()]),
                 Geometry::Line(_) => Kind::Geometry(vec!["line".to_string()]),
@@ -283,19 +1040,30 @@ impl AnalyzerContext {
             Value::Thing(thing) => Kind::Record(vec![Table::from(thing.tb.clone())]),
             Value::Table(table) => Kind::Record(vec![table.clone()]),
             Value::Range(_) => Kind::Range,
-            Value::Function(_) => Kind::Function(None, None),
+            Value::Function(function) => analyze_function(self, function)?,
+            Value::Expression(expression) => analyze_expression(self, expression)?,
             Value::Model(_) => Kind::Object,
+            Value::Cast(cast) => {
+                self.resolve(&cast.1)?;
+                cast.0.clone()
+            }
+            Value::Param(param) => match self.get_param(param.as_str()) {
+                Some(kind) => kind.clone(),
+                None if param.as_str() == "auth" => self.auth_kind()?,
+                None if param.as_str() == "token" => self.token_kind()?,
+                None => self
+                    .get_inferred_param(param.as_str())
+                    .cloned()
+                    .unwrap_or(Kind::Any),
+            },
+            Value::Idiom(idiom) => resolve_idiom(self, idiom)?,
+            Value::Subquery(subquery) => analyze_subquery(self, subquery)?,
+            Value::Block(block) => analyze_block(self, block)?,
             Value::Mock(_)
-            | Value::Param(_)
-            | Value::Idiom(_)
             | Value::Regex(_)
-            | Value::Cast(_)
-            | Value::Block(_)
             | Value::Edges(_)
             | Value::Future(_)
             | Value::Constant(_)
-            | Value::Subquery(_)
-            | Value::Expression(_)
             | Value::Query(_)
             | Value::Closure(_) => Kind::Any,
             _ => {
@@ -306,3 +1074,53 @@ impl AnalyzerContext {
         })
     }
 }
+
+/// Returns the path of a field as its definitions are keyed, such as
+/// `address.city` or `tags[*]`. Unlike when the idiom is printed, keywords and
+/// names such as `first-name` aren't escaped.
+pub(crate) fn field_path(field: &[Part]) -> String {
+    field
+        .iter()
+        .enumerate()
+        .map(|(index, part)| match part {
+            Part::Field(ident) if index == 0 => ident.to_raw(),
+            Part::Field(ident) => format!(".{}", ident.to_raw()),
+            part => part.to_string(),
+        })
+        .collect()
+}
+
+/// Inserts the kind of the field at `path` into `fields`. Nested fields such as
+/// `address.city` go into the object holding them, so `address` becomes
+/// `{ city: string }`. Paths into arrays keep their dotted name.
+fn insert_field(fields: &mut BTreeMap<String, Kind>, path: &[Part], kind: Kind) {
+    match path {
+        [Part::Field(name), rest @ ..]
+            if !rest.is_empty() && rest.iter().all(|part| matches!(part, Part::Field(_))) =>
+        {
+            let parent = fields
+                .entry(name.to_string())
+                .or_insert_with(|| Kind::Literal(Literal::Object(BTreeMap::new())));
+            nest_field(parent, rest, kind);
+        }
+        _ => {
+            fields.insert(Idiom::from(path.to_vec()).to_string(), kind);
+        }
+    }
+}
+
+/// Inserts the kind of the field at `path` into the object kind `parent`.
+/// Fields nested in something other than an object are left out, as the kind
+/// of their parent already covers them.
+fn nest_field(parent: &mut Kind, path: &[Part], kind: Kind) {
+    match parent {
+        Kind::Literal(Literal::Object(fields)) => insert_field(fields, path, kind),
+        Kind::Object => {
+            let mut fields = BTreeMap::new();
+            insert_field(&mut fields, path, kind);
+            *parent = Kind::Literal(Literal::Object(fields));
+        }
+        Kind::Option(inner) => nest_field(inner, path, kind),
+        _ => {}
+    }
+}
@@ -24,13 +24,30 @@ pub enum AnalyzerError {
     #[error("Parameter '${0}' not found")]
     ParameterNotFound(String),
 
+    /// A parameter is used in places that expect incompatible kinds
+    #[error(
+        "Parameter '${param}' is used as {first} ({first_site}) and as {second} ({second_site})"
+    )]
+    ParameterConflict {
+        param: String,
+        first: String,
+        first_site: String,
+        second: String,
+        second_site: String,
+    },
+
     /// A referenced function is not defined or imported
     #[error("Function '{0}' not found")]
     FunctionNotFound(String),
 
-    /// A type mismatch occurred during analysis
-    #[error("Type mismatch: expected {expected}, found {found}")]
-    TypeMismatch { expected: String, found: String },
+    /// A type mismatch occurred during analysis, optionally on a table's field
+    #[error("Type mismatch{}: expected {expected}, found {found}", location(.table, .field))]
+    TypeMismatch {
+        expected: String,
+        found: String,
+        table: Option<String>,
+        field: Option<String>,
+    },
 
     /// A schema constraint or rule was violated
     #[error("Schema violation: {message}")]
@@ -61,6 +78,30 @@ pub enum AnalyzerError {
     #[error("No scope provided for $auth inference.")]
     MissingAuth,
 
+    /// A parameter derived from the session (`$token`) is used without an access method
+    #[error("No access method provided for ${0} inference.")]
+    MissingAccess(String),
+
+    /// A selected access method was not declared by DEFINE ACCESS
+    #[error("Access method '{0}' not found")]
+    AccessNotFound(String),
+
+    /// A full-text analyzer was not declared by DEFINE ANALYZER
+    #[error("Analyzer '{0}' not found")]
+    AnalyzerNotFound(String),
+
+    /// Something is defined again without OVERWRITE or IF NOT EXISTS
+    #[error("The {0} is already defined")]
+    DuplicateDefinition(String),
+
+    /// A REMOVE statement names something that isn't defined
+    #[error("The {0} is not defined")]
+    DefinitionNotFound(String),
+
+    /// BEGIN, COMMIT and CANCEL statements don't pair up into transactions
+    #[error("Invalid transaction: {0}")]
+    InvalidTransaction(String),
+
     #[error("Not implemented: {0}")]
     Unimplemented(String),
 }
@@ -71,6 +112,23 @@ impl AnalyzerError {
         Self::TypeMismatch {
             expected: expected.to_string(),
             found: found.to_string(),
+            table: None,
+            field: None,
+        }
+    }
+
+    /// Creates a type mismatch error for a value written to a table's field
+    pub fn field_type_mismatch(
+        table: impl Into<String>,
+        field: impl Into<String>,
+        expected: &Kind,
+        found: &Kind,
+    ) -> Self {
+        Self::TypeMismatch {
+            expected: expected.to_string(),
+            found: found.to_string(),
+            table: Some(table.into()),
+            field: Some(field.into()),
         }
     }
 
@@ -89,11 +147,18 @@ impl AnalyzerError {
                         message
                     )
             }
-            Self::TypeMismatch { expected, found } => {
+            Self::TypeMismatch {
+                expected,
+                found,
+                table,
+                field,
+            } => {
                 format!(
-                    "Type mismatch: expected {}, found {}\n\
+                    "Type mismatch{}: expected {}, found {}\n\
                          Suggestion: Convert the value to the expected type or update the schema.",
-                    expected, found
+                    location(table, field),
+                    expected,
+                    found
                 )
             }
             Self::FieldNotFound { field, context } => {
@@ -117,6 +182,19 @@ impl AnalyzerError {
                     param
                 )
             }
+            Self::ParameterConflict {
+                param,
+                first,
+                first_site,
+                second,
+                second_site,
+            } => {
+                format!(
+                    "Parameter '${}' is used as {} in `{}`, but as {} in `{}`\n\
+                         Suggestion: Use separate parameters, or make both usages expect the same type.",
+                    param, first, first_site, second, second_site
+                )
+            }
             Self::FunctionNotFound(func) => {
                 format!(
                     "Function '{}' not found\n\
@@ -166,10 +244,49 @@ impl AnalyzerError {
                         err
                     )
             }
-            Self::MissingAuth => {
-                format!(
-                    "Missing provided auth scope but $auth is used\n\
+            Self::MissingAuth => "Missing provided auth scope but $auth is used\n\
                          Suggestion: A scope must be provided when selecting from $auth!"
+                .to_string(),
+            Self::MissingAccess(param) => {
+                format!(
+                    "Missing provided access method but ${} is used\n\
+                         Suggestion: Select a DEFINE ACCESS method in the config or with an `@access` comment.",
+                    param
+                )
+            }
+            Self::AccessNotFound(access) => {
+                format!(
+                    "Access method '{}' not found\n\
+                         Suggestion: Define it using DEFINE ACCESS or check the access method name.",
+                    access
+                )
+            }
+            Self::AnalyzerNotFound(analyzer) => {
+                format!(
+                    "Analyzer '{}' not found\n\
+                         Suggestion: Define it using DEFINE ANALYZER or check the analyzer name.",
+                    analyzer
+                )
+            }
+            Self::DuplicateDefinition(definition) => {
+                format!(
+                    "The {} is already defined\n\
+                         Suggestion: Use DEFINE ... OVERWRITE to replace it, or IF NOT EXISTS to keep it.",
+                    definition
+                )
+            }
+            Self::DefinitionNotFound(definition) => {
+                format!(
+                    "The {} is not defined\n\
+                         Suggestion: Check the name, or use REMOVE ... IF EXISTS.",
+                    definition
+                )
+            }
+            Self::InvalidTransaction(message) => {
+                format!(
+                    "Invalid transaction: {}\n\
+                         Suggestion: Close every BEGIN with a COMMIT or CANCEL, and don't nest transactions.",
+                    message
                 )
             }
         }
@@ -196,6 +313,14 @@ impl AnalyzerError {
         }
     }
 
+    /// Creates a permission denied error
+    pub fn permission_denied(message: impl Into<String>, resource: impl Into<String>) -> Self {
+        Self::PermissionDenied {
+            message: message.into(),
+            resource: resource.into(),
+        }
+    }
+
     /// Returns true if this error represents a schema violation
     pub fn is_schema_violation(&self) -> bool {
         matches!(self, Self::SchemaViolation { .. })
@@ -217,3 +342,12 @@ impl AnalyzerError {
         )
     }
 }
+
+/// Describes where in the schema an error occurred, e.g. ` in field 'age' of table 'user'`.
+fn location(table: &Option<String>, field: &Option<String>) -> String {
+    match (table, field) {
+        (Some(table), Some(field)) => format!(" in field '{}' of table '{}'", field, table),
+        (Some(table), None) => format!(" in table '{}'", table),
+        _ => String::new(),
+    }
+}
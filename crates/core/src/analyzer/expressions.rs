@@ -0,0 +1,464 @@
+//! Type inference for unary and binary expressions.
+//!
+//! The rules follow SurrealQL operator semantics:
+//!
+//! - Arithmetic promotes numbers (`int` < `float` < `decimal`)
+//! - `datetime` and `duration` combine through `+` and `-`
+//! - `+` concatenates strings
+//! - Comparison, equality, matching and containment operators produce `bool`
+//! - `||`, `&&`, `??` and `?:` produce one of their operands
+//!
+//! Operands of unknown kind are accepted, so a result is only as precise as
+//! the operands it was computed from. A parameter of unknown kind compared
+//! with a known operand is inferred from it, e.g. `age > $min_age` infers
+//! `$min_age` as the kind of `age`.
+//!
+//! Full-text (`@@`) and vector (`<|k|>`, `<|k,ef|>`) searches must search a
+//! field with an index of the matching kind.
+use surrealdb::sql::{Expression, Kind, Literal, Number, Operator, Value};
+
+use super::context::{AnalyzerContext, IndexKind};
+use super::error::{AnalyzerError, AnalyzerResult};
+use super::kind::{union_of, KindExt};
+
+pub fn analyze_expression(ctx: &mut AnalyzerContext, expr: &Expression) -> AnalyzerResult<Kind> {
+    match expr {
+        Expression::Unary { o, v } => {
+            let operand = ctx.resolve(v)?;
+            analyze_unary(o, &operand)
+        }
+        Expression::Binary { l, o, r } => {
+            let mut left = ctx.resolve(l)?;
+            let mut right = ctx.resolve(r)?;
+            let site = expr.to_string();
+            if let Some(kind) = operand_param_kind(o, &right, true) {
+                if let Some(kind) = ctx.infer_param(l, &kind, &site)? {
+                    left = kind;
+                }
+            }
+            if let Some(kind) = operand_param_kind(o, &left, false) {
+                if let Some(kind) = ctx.infer_param(r, &kind, &site)? {
+                    right = kind;
+                }
+            }
+            check_index(ctx, o, l, r)?;
+            analyze_binary(o, &left, &right)
+        }
+        _ => Err(AnalyzerError::Unimplemented(format!(
+            "Analysis not implemented for expression {}",
+            expr
+        ))),
+    }
+}
+
+/// Determines the kind a parameter operand must have to be used with `op`
+/// against an operand of kind `other`, if it can be told.
+///
+/// `param_is_left` tells which side of the operator the parameter is on, which
+/// matters for containment: in `tags CONTAINS $tag` the parameter is an element,
+/// in `$tags CONTAINS tag` it is an array.
+fn operand_param_kind(op: &Operator, other: &Kind, param_is_left: bool) -> Option<Kind> {
+    if *other == Kind::Any {
+        return None;
+    }
+    let array_of = |kind: Kind| Kind::Array(Box::new(kind), None);
+
+    match op {
+        Operator::Equal
+        | Operator::Exact
+        | Operator::NotEqual
+        | Operator::LessThan
+        | Operator::LessThanOrEqual
+        | Operator::MoreThan
+        | Operator::MoreThanOrEqual
+        // Vector searches compare vectors of the same kind.
+        | Operator::Knn(..)
+        | Operator::Ann(..) => Some(other.clone()),
+        // Fuzzy and full-text matching take a string to match against.
+        Operator::Like | Operator::NotLike | Operator::Matches(_) => Some(Kind::String),
+        Operator::Contain | Operator::NotContain if param_is_left => Some(array_of(other.clone())),
+        Operator::Contain | Operator::NotContain => element_kind(other),
+        Operator::Inside | Operator::NotInside if param_is_left => element_kind(other),
+        Operator::Inside | Operator::NotInside => Some(array_of(other.clone())),
+        // Set operators relate two collections of the same elements.
+        Operator::ContainAll
+        | Operator::ContainAny
+        | Operator::ContainNone
+        | Operator::AllInside
+        | Operator::AnyInside
+        | Operator::NoneInside => element_kind(other).map(array_of),
+        _ => None,
+    }
+}
+
+/// Checks that a search operator searches `field` of the current document
+/// with an index of the kind it needs, and that vectors searched for have the
+/// index's dimension. `@N@` operators are recorded for `search::score(N)`.
+fn check_index(
+    ctx: &mut AnalyzerContext,
+    op: &Operator,
+    field: &Value,
+    query: &Value,
+) -> AnalyzerResult<()> {
+    let name = match op {
+        Operator::Matches(_) => "SEARCH",
+        // Searches with an explicit distance don't use an index.
+        Operator::Knn(_, None) => "MTREE",
+        Operator::Ann(..) => "HNSW",
+        _ => return Ok(()),
+    };
+    let indexed = |kind: &IndexKind| {
+        matches!(
+            (op, kind),
+            (Operator::Matches(_), IndexKind::Search { .. })
+                | (Operator::Knn(..), IndexKind::MTree { .. })
+                | (Operator::Ann(..), IndexKind::Hnsw { .. })
+        )
+    };
+    // Only fields of a table's records are indexed.
+    let (Value::Idiom(field), Some(Kind::Record(tables))) = (field, ctx.get_param("this")) else {
+        return Ok(());
+    };
+    let [table] = tables.as_slice() else {
+        return Ok(());
+    };
+    let table = table.0.clone();
+
+    let index = ctx
+        .get_indexes(&table)
+        .into_iter()
+        .find(|index| indexed(&index.kind) && index.fields == [field.clone()])
+        .cloned()
+        .ok_or_else(|| {
+            AnalyzerError::schema_violation(
+                format!("No {name} index covers this field"),
+                Some(&table),
+                Some(field.to_string()),
+            )
+        })?;
+
+    match (op, &index.kind, query) {
+        // Each reference names a single search of the statement.
+        (Operator::Matches(Some(reference)), _, _) if ctx.get_match_ref(*reference).is_some() => {
+            Err(AnalyzerError::schema_violation(
+                format!("The match reference @{reference}@ is used more than once"),
+                Some(&table),
+                Some(field.to_string()),
+            ))
+        }
+        (Operator::Matches(Some(reference)), _, _) => {
+            ctx.register_match_ref(*reference, index.clone());
+            Ok(())
+        }
+        (
+            _,
+            IndexKind::MTree { dimension, .. } | IndexKind::Hnsw { dimension, .. },
+            Value::Array(vector),
+        ) if vector.len() != usize::from(*dimension) => Err(AnalyzerError::schema_violation(
+            format!(
+                "The vector has {} dimensions, but the index has {}",
+                vector.len(),
+                dimension
+            ),
+            Some(&table),
+            Some(field.to_string()),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The kind of the elements of a collection: array items, or the characters
+/// of a string.
+fn element_kind(kind: &Kind) -> Option<Kind> {
+    match kind {
+        Kind::Array(inner, _) | Kind::Set(inner, _) => Some(*inner.clone()),
+        Kind::Literal(Literal::Array(items)) => Some(union_of(items.iter().cloned())),
+        Kind::String => Some(Kind::String),
+        Kind::Option(inner) => element_kind(inner),
+        _ => None,
+    }
+}
+
+fn analyze_unary(op: &Operator, operand: &Kind) -> AnalyzerResult<Kind> {
+    match op {
+        // Any value can be negated, the result is its truthiness inverted.
+        Operator::Not => Ok(Kind::Bool),
+        Operator::Neg => map_variants(operand, operand, |kind, _| match kind {
+            Kind::Any => Ok(Kind::Number),
+            kind if kind.is_numeric() => Ok(numeric_kind(kind)),
+            kind => Err(AnalyzerError::type_mismatch(&Kind::Number, kind)),
+        }),
+        _ => Err(AnalyzerError::UnexpectedSyntax),
+    }
+}
+
+fn analyze_binary(op: &Operator, left: &Kind, right: &Kind) -> AnalyzerResult<Kind> {
+    match op {
+        // Logical operators return whichever operand decided the outcome.
+        Operator::Or | Operator::And => Ok(union_of([left.clone(), right.clone()])),
+        // `??` falls back when the left side is NONE or NULL, so only the
+        // defined part of the left side can be returned.
+        Operator::Nco => Ok(match left.without_none() {
+            Kind::Null => right.clone(),
+            left => union_of([left, right.clone()]),
+        }),
+        // `?:` falls back when the left side is falsy.
+        Operator::Tco => Ok(union_of([left.clone(), right.clone()])),
+
+        Operator::Add | Operator::Inc => map_variants(left, right, add),
+        Operator::Sub | Operator::Dec => map_variants(left, right, subtract),
+        Operator::Mul | Operator::Div | Operator::Rem | Operator::Pow => {
+            map_variants(left, right, |left, right| arithmetic(op, left, right))
+        }
+        // Extending an array appends the new values to it.
+        Operator::Ext => Ok(left.clone()),
+
+        // Everything else compares, matches or tests containment.
+        _ => Ok(Kind::Bool),
+    }
+}
+
+/// Applies `f` to every combination of union members of `left` and `right`,
+/// producing the union of the results.
+///
+/// NONE is removed from both sides first: an optional operand is only checked
+/// against the kind it holds when it is defined.
+fn map_variants(
+    left: &Kind,
+    right: &Kind,
+    f: impl Fn(&Kind, &Kind) -> AnalyzerResult<Kind>,
+) -> AnalyzerResult<Kind> {
+    let variants = |kind: &Kind| match kind.without_none() {
+        Kind::Either(kinds) => kinds,
+        kind => vec![kind],
+    };
+
+    let mut results = Vec::new();
+    for left in variants(left) {
+        for right in variants(right) {
+            results.push(f(&left, &right)?);
+        }
+    }
+    Ok(union_of(results))
+}
+
+fn add(left: &Kind, right: &Kind) -> AnalyzerResult<Kind> {
+    match (left, right) {
+        (Kind::Any, Kind::Any) => Ok(Kind::Any),
+        (Kind::String | Kind::Literal(Literal::String(_)), _)
+        | (_, Kind::String | Kind::Literal(Literal::String(_))) => {
+            expect(&Kind::String, left, right)?;
+            Ok(Kind::String)
+        }
+        (Kind::Datetime, _) | (_, Kind::Datetime) => {
+            let other = if *left == Kind::Datetime { right } else { left };
+            expect(&Kind::Duration, other, other)?;
+            Ok(Kind::Datetime)
+        }
+        (Kind::Duration | Kind::Literal(Literal::Duration(_)), _)
+        | (_, Kind::Duration | Kind::Literal(Literal::Duration(_))) => {
+            expect(&Kind::Duration, left, right)?;
+            Ok(Kind::Duration)
+        }
+        _ => arithmetic(&Operator::Add, left, right),
+    }
+}
+
+fn subtract(left: &Kind, right: &Kind) -> AnalyzerResult<Kind> {
+    match (left, right) {
+        (Kind::Any, Kind::Any) => Ok(Kind::Any),
+        (Kind::Datetime, Kind::Datetime) => Ok(Kind::Duration),
+        // The difference to an unknown operand may be a duration or a datetime.
+        (Kind::Datetime, Kind::Any) | (Kind::Any, Kind::Datetime) => Ok(Kind::Any),
+        (Kind::Datetime, other) | (other, Kind::Datetime) => {
+            expect(&Kind::Duration, other, other)?;
+            Ok(Kind::Datetime)
+        }
+        (Kind::Duration | Kind::Literal(Literal::Duration(_)), _)
+        | (_, Kind::Duration | Kind::Literal(Literal::Duration(_))) => {
+            expect(&Kind::Duration, left, right)?;
+            Ok(Kind::Duration)
+        }
+        _ => arithmetic(&Operator::Sub, left, right),
+    }
+}
+
+/// Types numeric arithmetic, promoting the operands to the wider number kind.
+fn arithmetic(op: &Operator, left: &Kind, right: &Kind) -> AnalyzerResult<Kind> {
+    expect(&Kind::Number, left, right)?;
+
+    let (left, right) = (numeric_kind(left), numeric_kind(right));
+    Ok(match (left, right) {
+        // Integer division yields a float when the division is not exact.
+        (Kind::Int, Kind::Int) if *op == Operator::Div => Kind::Number,
+        (Kind::Decimal, _) | (_, Kind::Decimal) => Kind::Decimal,
+        (Kind::Float, _) | (_, Kind::Float) => Kind::Float,
+        (Kind::Int, Kind::Int) => Kind::Int,
+        _ => Kind::Number,
+    })
+}
+
+/// Checks that both operands fit `expected`, reporting the first that doesn't.
+fn expect(expected: &Kind, left: &Kind, right: &Kind) -> AnalyzerResult<()> {
+    for found in [left, right] {
+        if !expected.is_assignable_from(found) {
+            return Err(AnalyzerError::type_mismatch(expected, found));
+        }
+    }
+    Ok(())
+}
+
+/// Maps a numeric kind to the number kind it behaves as in arithmetic.
+fn numeric_kind(kind: &Kind) -> Kind {
+    match kind {
+        Kind::Literal(Literal::Number(Number::Int(_))) => Kind::Int,
+        Kind::Literal(Literal::Number(Number::Float(_))) => Kind::Float,
+        Kind::Literal(Literal::Number(Number::Decimal(_))) => Kind::Decimal,
+        Kind::Int | Kind::Float | Kind::Decimal => kind.clone(),
+        _ => Kind::Number,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn numeric_promotion() {
+        let mut ctx = AnalyzerContext::new();
+
+        let analyzed_kind = analyze(&mut ctx, "1 + 2; 1 + 2.5; 2 * 3dec; -4; 7 / 2;")
+            .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[int, float, decimal, int, number]"));
+    }
+
+    #[test]
+    fn datetime_and_duration_arithmetic() {
+        let mut ctx = AnalyzerContext::new();
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            "time::now() - 1d; 1h + 30m; time::now() - d'2024-01-01T00:00:00Z';",
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[datetime, duration, duration]"));
+    }
+
+    #[test]
+    fn comparisons_and_logic() {
+        let mut ctx = AnalyzerContext::new();
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            "'a' + 'b'; 1 < 2; [1, 2] CONTAINS 1; !true; NONE ?? 'fallback'; 1 || 'one';",
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[string, bool, bool, bool, string, int | string]")
+        );
+    }
+
+    #[test]
+    fn params_from_conditions() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD email ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+                DEFINE FIELD tags ON user TYPE array<string>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            r#"
+            SELECT * FROM user
+            WHERE age > $min_age
+                AND email = $email
+                AND tags CONTAINS $tag
+                AND tags CONTAINSANY $any_tags
+                AND name IN $names
+                AND name ~ $search
+                AND string::starts_with(name, $prefix);
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            ctx.get_all_inferred_params(),
+            [
+                ("min_age".to_string(), kind!("int")),
+                ("email".to_string(), kind!("string")),
+                ("tag".to_string(), kind!("string")),
+                ("any_tags".to_string(), kind!("array<string>")),
+                ("names".to_string(), kind!("array<string>")),
+                ("search".to_string(), kind!("string")),
+                ("prefix".to_string(), kind!("string")),
+            ]
+        );
+    }
+
+    #[test]
+    fn params_are_unified() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD nickname ON user TYPE option<string>;
+                DEFINE FIELD age ON user TYPE int;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            r#"
+            SELECT * FROM user WHERE nickname = $name OR name = $name;
+            UPDATE user SET age = $age WHERE age > $age;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            ctx.get_all_inferred_params(),
+            [
+                ("name".to_string(), kind!("string")),
+                ("age".to_string(), kind!("int")),
+            ]
+        );
+
+        let err = analyze(&mut ctx, "SELECT * FROM user WHERE id = $id OR age = $id;")
+            .expect_err("Analysis should fail");
+        match err {
+            AnalyzerError::ParameterConflict {
+                param,
+                first_site,
+                second_site,
+                ..
+            } => {
+                assert_eq!(param, "id");
+                assert_eq!(first_site, "id = $id");
+                assert_eq!(second_site, "age = $id");
+            }
+            err => panic!("Expected a parameter conflict, found {err}"),
+        }
+    }
+
+    #[test]
+    fn incompatible_operands() {
+        let mut ctx = AnalyzerContext::new();
+
+        let err = analyze(&mut ctx, "'a' - 1;").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
+
+        let err = analyze(&mut ctx, "time::now() + 'a';").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
+    }
+}
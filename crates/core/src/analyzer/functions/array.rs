@@ -2,7 +2,7 @@ use crate::analyzer::error::{AnalyzerError, AnalyzerResult};
 use surrealdb::sql::{Function, Kind};
 use super::AnalyzerContext;
 
-pub(super) fn analyze_array(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+pub(super) fn analyze_array(ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
     let name = func.name().ok_or(AnalyzerError::UnexpectedSyntax)?;
 
     // Get the specific function after the namespace
@@ -24,7 +24,7 @@ pub(super) fn analyze_array(ctx: &AnalyzerContext, func: &Function) -> AnalyzerR
         // Return single element from array
         Some("at" | "find" | "first" | "last" | "pop") => {
             if let Some(first_arg) = func.args().first() {
-                match ctx.resolve(first_arg)? {
+                match ctx.resolve_as(first_arg, &Kind::Array(Box::new(Kind::Any), None))? {
                     Kind::Array(inner_type, _) => Ok(*inner_type),
                     _ => Err(AnalyzerError::UnexpectedSyntax),
                 }
@@ -36,7 +36,7 @@ pub(super) fn analyze_array(ctx: &AnalyzerContext, func: &Function) -> AnalyzerR
         // Return flattened array
         Some("flatten") => {
             if let Some(first_arg) = func.args().first() {
-                match ctx.resolve(first_arg)? {
+                match ctx.resolve_as(first_arg, &Kind::Array(Box::new(Kind::Any), None))? {
                     Kind::Array(inner_type, _) => {
                         match *inner_type {
                             Kind::Array(most_inner, _) => Ok(Kind::Array(most_inner, None)),
@@ -2,7 +2,7 @@ use crate::analyzer::error::{AnalyzerError, AnalyzerResult};
 use surrealdb::sql::{Function, Kind};
 use super::AnalyzerContext;
 
-pub(super) fn analyze_crypto(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+pub(super) fn analyze_crypto(ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
     // Retrieve the full function name, e.g. "crypto::blake3" or "crypto::argon2::compare"
     let name = func.name().ok_or(AnalyzerError::UnexpectedSyntax)?;
     let segments: Vec<&str> = name.split("::").collect();
@@ -19,7 +19,7 @@ pub(super) fn analyze_crypto(ctx: &AnalyzerContext, func: &Function) -> Analyzer
                 // Each of these functions expects a single string argument and returns a string.
                 if let Some(arg) = func.args().first() {
                     // Ensure the argument is a string.
-                    match ctx.resolve(arg)? {
+                    match ctx.resolve_as(arg, &Kind::String)? {
                         Kind::String => Ok(Kind::String),
                         _ => Err(AnalyzerError::UnexpectedSyntax),
                     }
@@ -27,7 +27,7 @@ pub(super) fn analyze_crypto(ctx: &AnalyzerContext, func: &Function) -> Analyzer
                     Err(AnalyzerError::UnexpectedSyntax)
                 }
             },
-            _ => return Err(AnalyzerError::FunctionNotFound(name.to_string())),
+            _ => Err(AnalyzerError::FunctionNotFound(name.to_string())),
         }
     } else if segments.len() >= 3 {
         // We now expect functions in sub-modules like "argon2", "bcrypt", "pbkdf2" or "scrypt".
@@ -42,8 +42,8 @@ pub(super) fn analyze_crypto(ctx: &AnalyzerContext, func: &Function) -> Analyzer
                             return Err(AnalyzerError::UnexpectedSyntax);
                         }
                         // For compare we require both arguments to be strings.
-                        let first_arg = ctx.resolve(&args[0])?;
-                        let second_arg = ctx.resolve(&args[1])?;
+                        let first_arg = ctx.resolve_as(&args[0], &Kind::String)?;
+                        let second_arg = ctx.resolve_as(&args[1], &Kind::String)?;
                         match (first_arg, second_arg) {
                             (Kind::String, Kind::String) => Ok(Kind::Bool),
                             // For some compare functions (e.g. bcrypt::compare) the second argument
@@ -54,7 +54,7 @@ pub(super) fn analyze_crypto(ctx: &AnalyzerContext, func: &Function) -> Analyzer
                     "generate" => {
                         // The generate functions expect a single string argument and return a string.
                         if let Some(arg) = func.args().first() {
-                            match ctx.resolve(arg)? {
+                            match ctx.resolve_as(arg, &Kind::String)? {
                                 Kind::String => Ok(Kind::String),
                                 _ => Err(AnalyzerError::UnexpectedSyntax),
                             }
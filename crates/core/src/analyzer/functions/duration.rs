@@ -2,7 +2,7 @@ use crate::analyzer::error::{AnalyzerError, AnalyzerResult};
 use surrealdb::sql::{Function, Kind};
 use super::AnalyzerContext;
 
-pub(super) fn analyze_duration(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+pub(super) fn analyze_duration(ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
     // Get the full function name, e.g. "duration::days" or "duration::from::hours"
     let name = func.name().ok_or(AnalyzerError::UnexpectedSyntax)?;
     let segments: Vec<&str> = name.split("::").collect();
@@ -19,7 +19,7 @@ pub(super) fn analyze_duration(ctx: &AnalyzerContext, func: &Function) -> Analyz
         "days" | "hours" | "micros" | "millis" | "mins" | "nanos" | "secs" | "weeks" | "years" => {
             // Expect one argument: a duration value.
             if let Some(arg) = func.args().first() {
-                let arg_kind = ctx.resolve(arg)?;
+                let arg_kind = ctx.resolve_as(arg, &Kind::Duration)?;
                 // (Assuming your type system provides a duration kind.)
                 match arg_kind {
                     Kind::Duration => Ok(Kind::Number),
@@ -38,7 +38,7 @@ pub(super) fn analyze_duration(ctx: &AnalyzerContext, func: &Function) -> Analyz
             match *conversion {
                 "days" | "hours" | "micros" | "millis" | "mins" | "nanos" | "secs" | "weeks" => {
                     if let Some(arg) = func.args().first() {
-                        let arg_kind = ctx.resolve(arg)?;
+                        let arg_kind = ctx.resolve_as(arg, &Kind::Number)?;
                         // Here we expect a number (this might be either a floating-point or integer type
                         // depending on your implementation).
                         match arg_kind {
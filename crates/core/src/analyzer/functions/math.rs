@@ -2,7 +2,7 @@ use super::AnalyzerContext;
 use crate::analyzer::error::{AnalyzerError, AnalyzerResult};
 use surrealdb::sql::{Function, Kind};
 
-pub(super) fn analyze_math(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+pub(super) fn analyze_math(ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
     // Get the full function name – e.g. "math::abs" or "math::clamp"
     let name = func.name().ok_or(AnalyzerError::UnexpectedSyntax)?;
     let segments: Vec<&str> = name.split("::").collect();
@@ -32,7 +32,7 @@ pub(super) fn analyze_math(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
             if args.len() != 1 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            match ctx.resolve(&args[0])? {
+            match ctx.resolve_as(&args[0], &Kind::Number)? {
                 Kind::Number => Ok(Kind::Number),
                 _ => Err(AnalyzerError::UnexpectedSyntax),
             }
@@ -44,7 +44,10 @@ pub(super) fn analyze_math(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
             if args.len() != 2 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            match (ctx.resolve(&args[0])?, ctx.resolve(&args[1])?) {
+            match (
+                ctx.resolve_as(&args[0], &Kind::Number)?,
+                ctx.resolve_as(&args[1], &Kind::Number)?,
+            ) {
                 (Kind::Number, Kind::Number) => Ok(Kind::Number),
                 _ => Err(AnalyzerError::UnexpectedSyntax),
             }
@@ -59,7 +62,7 @@ pub(super) fn analyze_math(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             for arg in args {
-                if let Kind::Number = ctx.resolve(arg)? {
+                if let Kind::Number = ctx.resolve_as(arg, &Kind::Number)? {
                     continue;
                 } else {
                     return Err(AnalyzerError::UnexpectedSyntax);
@@ -74,7 +77,7 @@ pub(super) fn analyze_math(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             for arg in args {
-                if let Kind::Number = ctx.resolve(arg)? {
+                if let Kind::Number = ctx.resolve_as(arg, &Kind::Number)? {
                     continue;
                 } else {
                     return Err(AnalyzerError::UnexpectedSyntax);
@@ -89,7 +92,7 @@ pub(super) fn analyze_math(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             for arg in args {
-                if let Kind::Number = ctx.resolve(arg)? {
+                if let Kind::Number = ctx.resolve_as(arg, &Kind::Number)? {
                     continue;
                 } else {
                     return Err(AnalyzerError::UnexpectedSyntax);
@@ -104,7 +107,7 @@ pub(super) fn analyze_math(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             for arg in args {
-                if let Kind::Number = ctx.resolve(arg)? {
+                if let Kind::Number = ctx.resolve_as(arg, &Kind::Number)? {
                     continue;
                 } else {
                     return Err(AnalyzerError::UnexpectedSyntax);
@@ -116,13 +119,13 @@ pub(super) fn analyze_math(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         // -------------------------------------------------------------------
         // FUNCTIONS THAT TAKE ARRAY ARGUMENTS (or an array plus a number)
         // These functions are for aggregations. Most of them accept an array of numbers and return a number.
-        "mean" | "median" | "midhinge" | "min" | "mode" | "nearestrank" | "percentile"
+        "max" | "mean" | "median" | "midhinge" | "min" | "mode" | "nearestrank" | "percentile"
         | "product" | "stddev" | "sum" | "spread" | "trimean" | "variance" => {
             let args = func.args();
             if args.len() != 1 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            match ctx.resolve(&args[0])? {
+            match ctx.resolve_as(&args[0], &Kind::Array(Box::new(Kind::Any), None))? {
                 // Assuming your array type is Kind::Array(inner, _)
                 Kind::Array(_, _) => {
                     // You might want to ensure that the inner type is a Number.
@@ -137,10 +140,10 @@ pub(super) fn analyze_math(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
             if args.len() != 2 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            match ctx.resolve(&args[0])? {
+            match ctx.resolve_as(&args[0], &Kind::Array(Box::new(Kind::Any), None))? {
                 Kind::Array(inner, _) => {
                     // Ensure the second argument is a Number.
-                    if let Kind::Number = ctx.resolve(&args[1])? {
+                    if let Kind::Number = ctx.resolve_as(&args[1], &Kind::Number)? {
                         // Preserve the inner type of the array on output.
                         Ok(Kind::Array(inner, None))
                     } else {
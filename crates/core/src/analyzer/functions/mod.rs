@@ -1,6 +1,7 @@
-use surrealdb::sql::{Function, Kind};
+use surrealdb::sql::{Function, Kind, Value};
 use super::context::AnalyzerContext;
 use super::error::{AnalyzerError, AnalyzerResult};
+use super::kind::KindExt;
 
 mod crypto;
 mod array;
@@ -16,10 +17,23 @@ mod string;
 mod time;
 
 
-pub fn analyze_function(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
-    let name = func.name().ok_or(AnalyzerError::UnexpectedSyntax)?;
+pub fn analyze_function(ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+    // Parameters passed as arguments are inferred at this call.
+    ctx.enter_call(func.to_string());
+    let kind = analyze_call(ctx, func);
+    ctx.exit_call();
+    kind
+}
+
+fn analyze_call(ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+    let name = match func {
+        Function::Normal(name, _) => name,
+        Function::Custom(name, args) => return analyze_custom(ctx, name, args),
+        // Embedded scripts and closures may return anything.
+        _ => return Ok(Kind::Any),
+    };
 
-    match name.split("::").next() {
+    let kind = match name.split("::").next() {
         Some("array") => array::analyze_array(ctx, func),
         Some("crypto") => crypto::analyze_crypto(ctx, func),
         Some("duration") => duration::analyze_duration(ctx, func),
@@ -33,6 +47,24 @@ pub fn analyze_function(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResul
         Some("string") => string::analyze_string(ctx, func),
         Some("time") => time::analyze_time(ctx, func),
 
+        Some("bytes") => resolve_args(ctx, func, Kind::Int),
+        Some("geo") => match (name.split("::").nth(1), name.split("::").nth(2)) {
+            (Some("area" | "bearing" | "distance"), _) => resolve_args(ctx, func, Kind::Float),
+            (Some("centroid"), _) | (Some("hash"), Some("decode")) => {
+                resolve_args(ctx, func, Kind::Geometry(vec!["point".to_string()]))
+            }
+            (Some("hash"), Some("encode")) => resolve_args(ctx, func, Kind::String),
+            (Some("is"), _) => resolve_args(ctx, func, Kind::Bool),
+            _ => Err(AnalyzerError::FunctionNotFound(name.to_string())),
+        },
+        Some("not") => resolve_args(ctx, func, Kind::Bool),
+        Some("record") => match name.split("::").nth(1) {
+            Some("id") => resolve_args(ctx, func, Kind::Any),
+            Some("tb" | "table") => resolve_args(ctx, func, Kind::String),
+            Some("exists") => resolve_args(ctx, func, Kind::Bool),
+            _ => Err(AnalyzerError::FunctionNotFound(name.to_string())),
+        },
+
         Some("session") => Ok(Kind::String),
         Some("sleep") => Ok(Kind::Null),
         Some("count") => Ok(Kind::Int),
@@ -57,5 +89,203 @@ pub fn analyze_function(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResul
         },
 
         Some(_) | None => Err(AnalyzerError::FunctionNotFound(name.to_string())),
+    };
+
+    // The parser only accepts built-in functions surrealdb knows, so one the
+    // analyzer doesn't model yet may return anything.
+    match kind {
+        Err(AnalyzerError::FunctionNotFound(missing)) if name.starts_with(missing.as_str()) => {
+            Ok(Kind::Any)
+        }
+        kind => kind,
+    }
+}
+
+/// Resolves the arguments of a built-in function whose return kind doesn't
+/// depend on them, so fields and parameters they use are still checked.
+fn resolve_args(ctx: &mut AnalyzerContext, func: &Function, returns: Kind) -> AnalyzerResult<Kind> {
+    for arg in func.args() {
+        ctx.resolve(arg)?;
+    }
+    Ok(returns)
+}
+
+/// Analyzes a call to a user defined `fn::` function.
+///
+/// The call is checked against the signature registered by `DEFINE FUNCTION`:
+/// it may not pass more arguments than declared, and may only leave out
+/// trailing arguments that accept NONE. Parameters of unknown kind passed as
+/// arguments are inferred from the declared argument kinds. The call resolves
+/// to the function's return kind.
+fn analyze_custom(ctx: &mut AnalyzerContext, name: &str, args: &[Value]) -> AnalyzerResult<Kind> {
+    let function = format!("fn::{}", name);
+    let signature = ctx
+        .get_function(name)
+        .cloned()
+        .ok_or_else(|| AnalyzerError::FunctionNotFound(function.clone()))?;
+
+    if args.len() > signature.args.len() {
+        return Err(AnalyzerError::InvalidFunctionCall {
+            function,
+            message: format!(
+                "expected at most {} arguments, found {}",
+                signature.args.len(),
+                args.len()
+            ),
+        });
+    }
+    if let Some((missing, _)) = signature.args[args.len()..]
+        .iter()
+        .find(|(_, kind)| !kind.accepts_none())
+    {
+        return Err(AnalyzerError::InvalidFunctionCall {
+            function,
+            message: format!("missing required argument ${}", missing),
+        });
+    }
+
+    let site = ctx
+        .current_call()
+        .map_or_else(|| function.clone(), str::to_string);
+    for (arg, (_, expected)) in args.iter().zip(signature.args.iter()) {
+        if ctx.infer_param(arg, expected, &site)?.is_some() {
+            continue;
+        }
+
+        let found = ctx.resolve(arg)?;
+        if !expected.is_assignable_from(&found) {
+            return Err(AnalyzerError::type_mismatch(expected, &found));
+        }
+    }
+
+    Ok(signature.returns)
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn builtin_function_statement() {
+        let mut ctx = AnalyzerContext::new();
+
+        let analyzed_kind = analyze(&mut ctx, "time::now();").expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[datetime]"));
+
+        let analyzed_kind =
+            analyze(&mut ctx, "string::uppercase($name);").expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[string]"));
+    }
+
+    #[test]
+    fn custom_function_return_kind() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE FUNCTION fn::greet($name: string) -> string {
+                RETURN "Hello, " + $name;
+            };
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(&mut ctx, "fn::greet('Jane');").expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[string]"));
+    }
+
+    #[test]
+    fn builtin_namespaces() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD email ON user TYPE string;
+                DEFINE FIELD version ON user TYPE string;
+                DEFINE FIELD location ON user TYPE geometry<point>;
+                DEFINE FIELD avatar ON user TYPE bytes;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let stmt = r#"
+            SELECT record::id(id) AS key, record::tb(id) AS tb,
+                geo::distance(location, (0, 0)) AS distance, bytes::len(avatar) AS size,
+                not(true) AS flag, string::is::email(email) AS valid,
+                string::distance::levenshtein(email, 'a') AS edits,
+                string::similarity::jaro(email, 'a') AS similarity,
+                string::semver::major(version) AS major
+            FROM user;
+        "#;
+        let analyzed_kind = analyze(&mut ctx, stmt).expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!(
+                "[array<{ key: any, tb: string, distance: float, size: int, flag: bool, valid: bool, edits: int, similarity: float, major: any }>]"
+            )
+        );
+
+        let err = analyze(&mut ctx, "SELECT string::is::email(mail) AS valid FROM user;")
+            .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::FieldNotFound { .. }));
+    }
+
+    #[test]
+    fn unknown_custom_function() {
+        let mut ctx = AnalyzerContext::new();
+
+        let err = analyze(&mut ctx, "fn::missing();").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::FunctionNotFound(name) if name == "fn::missing"));
+    }
+
+    #[test]
+    fn function_in_create_content() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let err = analyze(&mut ctx, "CREATE user CONTENT { name: fn::shout('jane') };")
+            .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::FunctionNotFound(name) if name == "fn::shout"));
+    }
+
+    #[test]
+    fn function_return_kind_in_create_content() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+            DEFINE FUNCTION fn::shout($name: string) -> string {
+                RETURN string::uppercase($name);
+            };
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            CREATE ONLY user CONTENT { name: fn::shout('jane'), age: 30 };
+            CREATE ONLY user CONTENT { name: string::lowercase('JANE'), age: 30 };
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[{ name: string, age: int }, { name: string, age: int }]"));
+
+        let err = analyze(&mut ctx, "CREATE user CONTENT { name: math::abs(-1), age: 30 };")
+            .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
     }
 }
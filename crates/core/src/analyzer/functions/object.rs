@@ -11,7 +11,7 @@ use super::AnalyzerContext;
 /// • object::keys(object) -> array<string>
 /// • object::len(object) -> number
 /// • object::values(object) -> array<any>
-pub(super) fn analyze_object(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+pub(super) fn analyze_object(ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
     // Get the full function name, e.g. "object::entries"
     let name = func.name().ok_or(AnalyzerError::UnexpectedSyntax)?;
     // Get the specific function name after the "object" namespace.
@@ -28,7 +28,7 @@ pub(super) fn analyze_object(ctx: &AnalyzerContext, func: &Function) -> Analyzer
         "entries" => {
             // object::entries(object) -> array of entry pairs
             // Ensure the provided argument is an object.
-            match ctx.resolve(arg)? {
+            match ctx.resolve_as(arg, &Kind::Object)? {
                 Kind::Object => {
                     // Return an array where each entry is itself an array of two elements:
                     // a string (the key) and an unknown/dynamic type (the value).
@@ -43,7 +43,7 @@ pub(super) fn analyze_object(ctx: &AnalyzerContext, func: &Function) -> Analyzer
         "from_entries" => {
             // object::from_entries(array) -> object
             // Expect an array (with inner type array-of-[string, any] pairs) as input.
-            match ctx.resolve(arg)? {
+            match ctx.resolve_as(arg, &Kind::Array(Box::new(Kind::Any), None))? {
                 Kind::Array(_, _) => Ok(Kind::Object),
                 _ => Err(AnalyzerError::UnexpectedSyntax),
             }
@@ -51,7 +51,7 @@ pub(super) fn analyze_object(ctx: &AnalyzerContext, func: &Function) -> Analyzer
 
         "is_empty" => {
             // object::is_empty(object) -> bool
-            match ctx.resolve(arg)? {
+            match ctx.resolve_as(arg, &Kind::Object)? {
                 Kind::Object => Ok(Kind::Bool),
                 _ => Err(AnalyzerError::UnexpectedSyntax),
             }
@@ -59,7 +59,7 @@ pub(super) fn analyze_object(ctx: &AnalyzerContext, func: &Function) -> Analyzer
 
         "keys" => {
             // object::keys(object) -> array<string>
-            match ctx.resolve(arg)? {
+            match ctx.resolve_as(arg, &Kind::Object)? {
                 Kind::Object => Ok(Kind::Array(Box::new(Kind::String), None)),
                 _ => Err(AnalyzerError::UnexpectedSyntax),
             }
@@ -67,7 +67,7 @@ pub(super) fn analyze_object(ctx: &AnalyzerContext, func: &Function) -> Analyzer
 
         "len" => {
             // object::len(object) -> number
-            match ctx.resolve(arg)? {
+            match ctx.resolve_as(arg, &Kind::Object)? {
                 Kind::Object => Ok(Kind::Number),
                 _ => Err(AnalyzerError::UnexpectedSyntax),
             }
@@ -75,7 +75,7 @@ pub(super) fn analyze_object(ctx: &AnalyzerContext, func: &Function) -> Analyzer
 
         "values" => {
             // object::values(object) -> array<any>
-            match ctx.resolve(arg)? {
+            match ctx.resolve_as(arg, &Kind::Object)? {
                 Kind::Object => Ok(Kind::Array(Box::new(Kind::Any), None)),
                 _ => Err(AnalyzerError::UnexpectedSyntax),
             }
@@ -2,7 +2,7 @@ use crate::analyzer::error::{AnalyzerError, AnalyzerResult};
 use surrealdb::sql::{Function, Kind};
 use super::AnalyzerContext;
 
-pub(super) fn analyze_rand(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+pub(super) fn analyze_rand(ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
     let name = func.name().ok_or(AnalyzerError::UnexpectedSyntax)?;
     let segments: Vec<&str> = name.split("::").collect();
 
@@ -14,7 +14,7 @@ pub(super) fn analyze_rand(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         return Ok(Kind::Number);
     }
 
-    if segments.get(0) != Some(&"rand") {
+    if segments.first() != Some(&"rand") {
         return Err(AnalyzerError::FunctionNotFound(name.to_string()));
     }
 
@@ -42,8 +42,8 @@ pub(super) fn analyze_rand(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
                 match args.len() {
                     0 => Ok(Kind::Number),
                     2 => {
-                        let first = ctx.resolve(&args[0])?;
-                        let second = ctx.resolve(&args[1])?;
+                        let first = ctx.resolve_as(&args[0], &Kind::Number)?;
+                        let second = ctx.resolve_as(&args[1], &Kind::Number)?;
                         match (first, second) {
                             (Kind::Number, Kind::Number) => Ok(Kind::Number),
                             _ => Err(AnalyzerError::UnexpectedSyntax),
@@ -65,7 +65,7 @@ pub(super) fn analyze_rand(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
                     let args = func.args();
                     match args.len() {
                         0 => Ok(Kind::Uuid),
-                        1 => match ctx.resolve(&args[0])? {
+                        1 => match ctx.resolve_as(&args[0], &Kind::Datetime)? {
                             Kind::Datetime => Ok(Kind::Uuid),
                             _ => Err(AnalyzerError::UnexpectedSyntax),
                         },
@@ -78,7 +78,7 @@ pub(super) fn analyze_rand(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
                 let args = func.args();
                 match args.len() {
                     0 => Ok(Kind::Uuid), // or Kind::Ulid if available
-                    1 => match ctx.resolve(&args[0])? {
+                    1 => match ctx.resolve_as(&args[0], &Kind::Datetime)? {
                         Kind::Datetime => Ok(Kind::Uuid),
                         _ => Err(AnalyzerError::UnexpectedSyntax),
                     },
@@ -1,5 +1,6 @@
+use crate::analyzer::context::{IndexKind, TableIndex};
 use crate::analyzer::error::{AnalyzerError, AnalyzerResult};
-use surrealdb::sql::{Function, Kind};
+use surrealdb::sql::{Function, Kind, Value};
 use super::AnalyzerContext;
 
 /// Analyze functions operating in the "search" namespace.
@@ -9,7 +10,11 @@ use super::AnalyzerContext;
 ///   • search::score(number) -> number
 ///   • search::highlight(string, string, number, [boolean]) -> string
 ///   • search::offsets(number, [boolean]) -> object
-pub(super) fn analyze_search(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+///
+/// The number passed to score, highlight and offsets refers to an `@N@`
+/// operator of the statement, whose index must store HIGHLIGHTS for highlight
+/// and offsets.
+pub(super) fn analyze_search(ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
     // Retrieve the full function name, e.g. "search::analyze"
     let name = func.name().ok_or(AnalyzerError::UnexpectedSyntax)?;
     let segments: Vec<&str> = name.split("::").collect();
@@ -26,8 +31,15 @@ pub(super) fn analyze_search(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 2 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
+            // The analyzer must exist, when it is named by a literal.
+            if let Value::Strand(analyzer) = &args[0] {
+                ctx.check_analyzer(analyzer)?;
+            }
             // Both arguments should be strings.
-            match (ctx.resolve(&args[0])?, ctx.resolve(&args[1])?) {
+            match (
+                ctx.resolve_as(&args[0], &Kind::String)?,
+                ctx.resolve_as(&args[1], &Kind::String)?,
+            ) {
                 (Kind::String, Kind::String) => {
                     // Return an array of strings.
                     Ok(Kind::Array(Box::new(Kind::String), None))
@@ -42,7 +54,8 @@ pub(super) fn analyze_search(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 1 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if let Kind::Number = ctx.resolve(&args[0])? {
+            if let Kind::Number = ctx.resolve_as(&args[0], &Kind::Number)? {
+                check_match_ref(ctx, "search::score", &args[0], false)?;
                 Ok(Kind::Number)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -57,20 +70,21 @@ pub(super) fn analyze_search(ctx: &AnalyzerContext, func: &Function) -> Analyzer
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             // First argument: prefix string.
-            if let Kind::String = ctx.resolve(&args[0])? {} else {
+            if let Kind::String = ctx.resolve_as(&args[0], &Kind::String)? {} else {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             // Second argument: suffix string.
-            if let Kind::String = ctx.resolve(&args[1])? {} else {
+            if let Kind::String = ctx.resolve_as(&args[1], &Kind::String)? {} else {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             // Third argument: number (predicate reference).
-            if let Kind::Number = ctx.resolve(&args[2])? {} else {
+            if let Kind::Number = ctx.resolve_as(&args[2], &Kind::Number)? {} else {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
+            check_match_ref(ctx, "search::highlight", &args[2], true)?;
             // Fourth argument (if given): boolean.
             if args.len() == 4 {
-                if let Kind::Bool = ctx.resolve(&args[3])? {} else {
+                if let Kind::Bool = ctx.resolve_as(&args[3], &Kind::Bool)? {} else {
                     return Err(AnalyzerError::UnexpectedSyntax);
                 }
             }
@@ -82,17 +96,18 @@ pub(super) fn analyze_search(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             // API: search::offsets(number, [boolean]) -> object
             let args = func.args();
             // Expect either 1 or 2 arguments.
-            if args.len() < 1 || args.len() > 2 {
+            if !(1..=2).contains(&args.len()) {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             // First argument must be a number.
-            if let Kind::Number = ctx.resolve(&args[0])? {
+            if let Kind::Number = ctx.resolve_as(&args[0], &Kind::Number)? {
             } else {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
+            check_match_ref(ctx, "search::offsets", &args[0], true)?;
             // Optional second argument must be a boolean.
             if args.len() == 2 {
-                if let Kind::Bool = ctx.resolve(&args[1])? {
+                if let Kind::Bool = ctx.resolve_as(&args[1], &Kind::Bool)? {
                 } else {
                     return Err(AnalyzerError::UnexpectedSyntax);
                 }
@@ -103,3 +118,36 @@ pub(super) fn analyze_search(ctx: &AnalyzerContext, func: &Function) -> Analyzer
         other => Err(AnalyzerError::FunctionNotFound(format!("search::{}", other))),
     }
 }
+
+/// Checks that a match reference, such as the `1` of `search::score(1)`, refers
+/// to an `@1@` operator of the statement, searching an index that stores
+/// highlights when `highlights` is set.
+fn check_match_ref(
+    ctx: &AnalyzerContext,
+    function: &str,
+    reference: &Value,
+    highlights: bool,
+) -> AnalyzerResult<()> {
+    // References computed at runtime can't be checked.
+    let Value::Number(number) = reference else {
+        return Ok(());
+    };
+    let reference = number.to_int();
+    let index = u8::try_from(reference)
+        .ok()
+        .and_then(|reference| ctx.get_match_ref(reference));
+
+    let message = match index {
+        None => format!("no @{}@ operator searches a full-text index", reference),
+        Some(TableIndex {
+            name,
+            kind: IndexKind::Search { highlights: false, .. },
+            ..
+        }) if highlights => format!("the SEARCH index '{}' doesn't store HIGHLIGHTS", name),
+        Some(_) => return Ok(()),
+    };
+    Err(AnalyzerError::InvalidFunctionCall {
+        function: function.to_string(),
+        message,
+    })
+}
@@ -11,14 +11,17 @@ use super::AnalyzerContext;
 ///
 ///   • string::concat(string, ...) -> string
 ///   • string::contains(string, string) -> bool
+///   • string::distance::*(string, string) -> int, or float when normalized
 ///   • string::ends_with(string, string) -> bool
+///   • string::is::*(any) -> bool
 ///   • string::join(string, string...) -> string
-///   • string::len(string) -> number
+///   • string::len(string) -> int
 ///   • string::lowercase(string) -> string
 ///   • string::matches(string, string) -> bool
 ///   • string::repeat(string, number) -> string
 ///   • string::replace(string, string, string) -> string
 ///   • string::reverse(string) -> string
+///   • string::similarity::*(string, string) -> int, or float for ratios
 ///   • string::slice(string, number, number) -> string
 ///   • string::slug(string) -> string
 ///   • string::split(string, string) -> array<string>
@@ -27,7 +30,7 @@ use super::AnalyzerContext;
 ///   • string::uppercase(string) -> string
 ///   • string::words(string) -> array<string>
 ///
-pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+pub(super) fn analyze_string(ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
     let name = func.name().ok_or(AnalyzerError::UnexpectedSyntax)?;
 
     // Get the specific function name using string::[func]
@@ -39,7 +42,7 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             }
             // Optionally, verify that every argument is a string.
             for arg in func.args() {
-                if ctx.resolve(arg)? != Kind::String {
+                if ctx.resolve_as(arg, &Kind::String)? != Kind::String {
                     return Err(AnalyzerError::UnexpectedSyntax);
                 }
             }
@@ -51,7 +54,9 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 2 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(&args[0])? == Kind::String && ctx.resolve(&args[1])? == Kind::String {
+            if ctx.resolve_as(&args[0], &Kind::String)? == Kind::String
+                && ctx.resolve_as(&args[1], &Kind::String)? == Kind::String
+            {
                 Ok(Kind::Bool)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -63,7 +68,9 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 2 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(&args[0])? == Kind::String && ctx.resolve(&args[1])? == Kind::String {
+            if ctx.resolve_as(&args[0], &Kind::String)? == Kind::String
+                && ctx.resolve_as(&args[1], &Kind::String)? == Kind::String
+            {
                 Ok(Kind::Bool)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -76,19 +83,19 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             for arg in args {
-                if ctx.resolve(arg)? != Kind::String {
+                if ctx.resolve_as(arg, &Kind::String)? != Kind::String {
                     return Err(AnalyzerError::UnexpectedSyntax);
                 }
             }
             Ok(Kind::String)
         },
-        // string::len(string) -> number
+        // string::len(string) -> int
         Some("len") => {
             if func.args().len() != 1 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(func.args().first().unwrap())? == Kind::String {
-                Ok(Kind::Number)
+            if ctx.resolve_as(func.args().first().unwrap(), &Kind::String)? == Kind::String {
+                Ok(Kind::Int)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
             }
@@ -98,7 +105,7 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if func.args().len() != 1 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(func.args().first().unwrap())? == Kind::String {
+            if ctx.resolve_as(func.args().first().unwrap(), &Kind::String)? == Kind::String {
                 Ok(Kind::String)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -110,7 +117,9 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 2 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(&args[0])? == Kind::String && ctx.resolve(&args[1])? == Kind::String {
+            if ctx.resolve_as(&args[0], &Kind::String)? == Kind::String
+                && ctx.resolve_as(&args[1], &Kind::String)? == Kind::String
+            {
                 Ok(Kind::Bool)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -122,7 +131,9 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 2 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(&args[0])? == Kind::String && ctx.resolve(&args[1])? == Kind::Number {
+            if ctx.resolve_as(&args[0], &Kind::String)? == Kind::String
+                && ctx.resolve_as(&args[1], &Kind::Number)? == Kind::Number
+            {
                 Ok(Kind::String)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -134,9 +145,9 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 3 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(&args[0])? == Kind::String &&
-               ctx.resolve(&args[1])? == Kind::String &&
-               ctx.resolve(&args[2])? == Kind::String {
+            if ctx.resolve_as(&args[0], &Kind::String)? == Kind::String &&
+               ctx.resolve_as(&args[1], &Kind::String)? == Kind::String &&
+               ctx.resolve_as(&args[2], &Kind::String)? == Kind::String {
                 Ok(Kind::String)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -147,7 +158,7 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if func.args().len() != 1 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(func.args().first().unwrap())? == Kind::String {
+            if ctx.resolve_as(func.args().first().unwrap(), &Kind::String)? == Kind::String {
                 Ok(Kind::String)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -159,9 +170,9 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 3 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(&args[0])? == Kind::String &&
-               ctx.resolve(&args[1])? == Kind::Number &&
-               ctx.resolve(&args[2])? == Kind::Number {
+            if ctx.resolve_as(&args[0], &Kind::String)? == Kind::String &&
+               ctx.resolve_as(&args[1], &Kind::Number)? == Kind::Number &&
+               ctx.resolve_as(&args[2], &Kind::Number)? == Kind::Number {
                 Ok(Kind::String)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -172,7 +183,7 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if func.args().len() != 1 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(func.args().first().unwrap())? == Kind::String {
+            if ctx.resolve_as(func.args().first().unwrap(), &Kind::String)? == Kind::String {
                 Ok(Kind::String)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -184,7 +195,9 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 2 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(&args[0])? == Kind::String && ctx.resolve(&args[1])? == Kind::String {
+            if ctx.resolve_as(&args[0], &Kind::String)? == Kind::String
+                && ctx.resolve_as(&args[1], &Kind::String)? == Kind::String
+            {
                 Ok(Kind::Array(Box::new(Kind::String), None))
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -196,7 +209,9 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 2 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(&args[0])? == Kind::String && ctx.resolve(&args[1])? == Kind::String {
+            if ctx.resolve_as(&args[0], &Kind::String)? == Kind::String
+                && ctx.resolve_as(&args[1], &Kind::String)? == Kind::String
+            {
                 Ok(Kind::Bool)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -207,7 +222,7 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if func.args().len() != 1 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(func.args().first().unwrap())? == Kind::String {
+            if ctx.resolve_as(func.args().first().unwrap(), &Kind::String)? == Kind::String {
                 Ok(Kind::String)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -218,7 +233,7 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if func.args().len() != 1 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(func.args().first().unwrap())? == Kind::String {
+            if ctx.resolve_as(func.args().first().unwrap(), &Kind::String)? == Kind::String {
                 Ok(Kind::String)
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
@@ -229,12 +244,28 @@ pub(super) fn analyze_string(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if func.args().len() != 1 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            if ctx.resolve(func.args().first().unwrap())? == Kind::String {
+            if ctx.resolve_as(func.args().first().unwrap(), &Kind::String)? == Kind::String {
                 Ok(Kind::Array(Box::new(Kind::String), None))
             } else {
                 Err(AnalyzerError::UnexpectedSyntax)
             }
         },
+        // string::distance::*(string, string) -> int, or float when normalized
+        Some("distance") => match name.split("::").nth(2) {
+            Some(distance) if distance.starts_with("normalized_") => {
+                super::resolve_args(ctx, func, Kind::Float)
+            }
+            _ => super::resolve_args(ctx, func, Kind::Int),
+        },
+        // string::is::*(any) -> bool
+        Some("is") => super::resolve_args(ctx, func, Kind::Bool),
+        // string::similarity::*(string, string) -> int, or float for ratios
+        Some("similarity") => match name.split("::").nth(2) {
+            Some("jaro" | "jaro_winkler" | "sorensen_dice") => {
+                super::resolve_args(ctx, func, Kind::Float)
+            }
+            _ => super::resolve_args(ctx, func, Kind::Int),
+        },
         Some(other) => Err(AnalyzerError::FunctionNotFound(format!("string::{}", other))),
         None => Err(AnalyzerError::UnexpectedSyntax),
     }
@@ -41,7 +41,7 @@ use super::AnalyzerContext;
 ///   • time::from::ulid(string) -> datetime
 ///   • time::from::uuid(uuid) -> datetime
 ///
-pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+pub(super) fn analyze_time(ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
     // Retrieve the full function name (e.g. "time::ceil", "time::from::millis", etc.)
     let name = func.name().ok_or(AnalyzerError::UnexpectedSyntax)?;
     let segments: Vec<&str> = name.split("::").collect();
@@ -56,7 +56,10 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"ceil") => {
             let args = func.args();
             if args.len() != 2 { return Err(AnalyzerError::UnexpectedSyntax); }
-            match (ctx.resolve(&args[0])?, ctx.resolve(&args[1])?) {
+            match (
+                ctx.resolve_as(&args[0], &Kind::Datetime)?,
+                ctx.resolve_as(&args[1], &Kind::Duration)?,
+            ) {
                 (Kind::Datetime, Kind::Duration) => Ok(Kind::Datetime),
                 _ => Err(AnalyzerError::UnexpectedSyntax),
             }
@@ -66,10 +69,8 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"day") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 {
-                if ctx.resolve(args.first().unwrap())? != Kind::Datetime {
-                    return Err(AnalyzerError::UnexpectedSyntax);
-                }
+            if args.len() == 1 && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime {
+                return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
         },
@@ -78,7 +79,10 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"floor") => {
             let args = func.args();
             if args.len() != 2 { return Err(AnalyzerError::UnexpectedSyntax); }
-            match (ctx.resolve(&args[0])?, ctx.resolve(&args[1])?) {
+            match (
+                ctx.resolve_as(&args[0], &Kind::Datetime)?,
+                ctx.resolve_as(&args[1], &Kind::Duration)?,
+            ) {
                 (Kind::Datetime, Kind::Duration) => Ok(Kind::Datetime),
                 _ => Err(AnalyzerError::UnexpectedSyntax),
             }
@@ -88,7 +92,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"format") => {
             let args = func.args();
             if args.len() != 2 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if ctx.resolve(&args[0])? != Kind::Datetime || ctx.resolve(&args[1])? != Kind::String {
+            if ctx.resolve_as(&args[0], &Kind::Datetime)? != Kind::Datetime
+                || ctx.resolve_as(&args[1], &Kind::String)? != Kind::String
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::String)
@@ -98,7 +104,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"group") => {
             let args = func.args();
             if args.len() != 2 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if ctx.resolve(&args[0])? != Kind::Datetime || ctx.resolve(&args[1])? != Kind::String {
+            if ctx.resolve_as(&args[0], &Kind::Datetime)? != Kind::Datetime
+                || ctx.resolve_as(&args[1], &Kind::String)? != Kind::String
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Datetime)
@@ -108,7 +116,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"hour") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 && ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+            if args.len() == 1
+                && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
@@ -118,7 +128,10 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"max") => {
             let args = func.args();
             if args.len() != 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            match ctx.resolve(args.first().unwrap())? {
+            match ctx.resolve_as(
+                args.first().unwrap(),
+                &Kind::Array(Box::new(Kind::Datetime), None),
+            )? {
                 Kind::Array(inner, _) => {
                     // For our purposes we check the inner type is a datetime.
                     if *inner != Kind::Datetime {
@@ -134,7 +147,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"micros") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 && ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+            if args.len() == 1
+                && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
@@ -144,7 +159,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"millis") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 && ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+            if args.len() == 1
+                && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
@@ -154,7 +171,10 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"min") => {
             let args = func.args();
             if args.len() != 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            match ctx.resolve(args.first().unwrap())? {
+            match ctx.resolve_as(
+                args.first().unwrap(),
+                &Kind::Array(Box::new(Kind::Datetime), None),
+            )? {
                 Kind::Array(inner, _) => {
                     if *inner != Kind::Datetime {
                         return Err(AnalyzerError::UnexpectedSyntax);
@@ -169,7 +189,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"minute") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 && ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+            if args.len() == 1
+                && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
@@ -179,7 +201,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"month") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 && ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+            if args.len() == 1
+                && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
@@ -189,7 +213,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"nano") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 && ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+            if args.len() == 1
+                && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
@@ -207,7 +233,10 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"round") => {
             let args = func.args();
             if args.len() != 2 { return Err(AnalyzerError::UnexpectedSyntax); }
-            match (ctx.resolve(&args[0])?, ctx.resolve(&args[1])?) {
+            match (
+                ctx.resolve_as(&args[0], &Kind::Datetime)?,
+                ctx.resolve_as(&args[1], &Kind::Duration)?,
+            ) {
                 (Kind::Datetime, Kind::Duration) => Ok(Kind::Datetime),
                 _ => Err(AnalyzerError::UnexpectedSyntax),
             }
@@ -217,7 +246,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"second") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 && ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+            if args.len() == 1
+                && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
@@ -235,7 +266,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"unix") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 && ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+            if args.len() == 1
+                && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
@@ -245,7 +278,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"wday") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 && ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+            if args.len() == 1
+                && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
@@ -255,7 +290,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"week") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 && ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+            if args.len() == 1
+                && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
@@ -265,7 +302,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"yday") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 && ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+            if args.len() == 1
+                && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
@@ -275,7 +314,9 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
         Some(&"year") => {
             let args = func.args();
             if args.len() > 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-            if args.len() == 1 && ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+            if args.len() == 1
+                && ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime
+            {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             Ok(Kind::Number)
@@ -290,7 +331,7 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
                 "leap_year" => {
                     let args = func.args();
                     if args.len() != 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-                    if ctx.resolve(args.first().unwrap())? != Kind::Datetime {
+                    if ctx.resolve_as(args.first().unwrap(), &Kind::Datetime)? != Kind::Datetime {
                         return Err(AnalyzerError::UnexpectedSyntax);
                     }
                     Ok(Kind::Bool)
@@ -309,7 +350,7 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
                 "micros" | "millis" | "nanos" | "secs" | "unix" => {
                     let args = func.args();
                     if args.len() != 1 { return Err(AnalyzerError::UnexpectedSyntax); }
-                    if ctx.resolve(args.first().unwrap())? != Kind::Number {
+                    if ctx.resolve_as(args.first().unwrap(), &Kind::Number)? != Kind::Number {
                         return Err(AnalyzerError::UnexpectedSyntax);
                     }
                     Ok(Kind::Datetime)
@@ -319,7 +360,7 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
                     let args = func.args();
                     if args.len() != 1 { return Err(AnalyzerError::UnexpectedSyntax); }
                     // Here we expect a string representing a ULID.
-                    if ctx.resolve(args.first().unwrap())? != Kind::String {
+                    if ctx.resolve_as(args.first().unwrap(), &Kind::String)? != Kind::String {
                         return Err(AnalyzerError::UnexpectedSyntax);
                     }
                     Ok(Kind::Datetime)
@@ -328,7 +369,7 @@ pub(super) fn analyze_time(ctx: &AnalyzerContext, func: &Function) -> AnalyzerRe
                     let args = func.args();
                     if args.len() != 1 { return Err(AnalyzerError::UnexpectedSyntax); }
                     // Here we expect a UUID type.
-                    if ctx.resolve(args.first().unwrap())? != Kind::Uuid {
+                    if ctx.resolve_as(args.first().unwrap(), &Kind::Uuid)? != Kind::Uuid {
                         return Err(AnalyzerError::UnexpectedSyntax);
                     }
                     Ok(Kind::Datetime)
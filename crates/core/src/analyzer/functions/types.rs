@@ -30,7 +30,7 @@ use surrealdb::sql::{Function, Kind};
 /// duration, float, geometry, int, line, none, null, multiline, multipoint,
 /// multipolygon, number, object, point, polygon, record (optionally 1–2 args),
 /// string, uuid.
-pub(super) fn analyze_type(_ctx: &AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+pub(super) fn analyze_type(_ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
     let name = func.name().ok_or(AnalyzerError::UnexpectedSyntax)?;
     let segments: Vec<&str> = name.split("::").collect();
 
@@ -128,7 +128,7 @@ pub(super) fn analyze_type(_ctx: &AnalyzerContext, func: &Function) -> AnalyzerR
         }
         "record" => {
             let arg_count = func.args().len();
-            if arg_count < 1 || arg_count > 2 {
+            if !(1..=2).contains(&arg_count) {
                 Err(AnalyzerError::UnexpectedSyntax)
             } else {
                 // Return a record type.
@@ -186,7 +186,7 @@ pub(super) fn analyze_type(_ctx: &AnalyzerContext, func: &Function) -> AnalyzerR
                 // The "record" type check can accept one or two arguments.
                 "record" => {
                     let len = func.args().len();
-                    if len < 1 || len > 2 {
+                    if !(1..=2).contains(&len) {
                         Err(AnalyzerError::UnexpectedSyntax)
                     } else {
                         Ok(Kind::Bool)
@@ -2,7 +2,7 @@ use crate::analyzer::error::{AnalyzerError, AnalyzerResult};
 use surrealdb::sql::{Function, Kind};
 use super::AnalyzerContext;
 
-pub(super) fn analyze_vector(ctx: &AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
+pub(super) fn analyze_vector(ctx: &mut AnalyzerContext, func: &Function) -> AnalyzerResult<Kind> {
     // Get full function name e.g. "vector::add"
     let name = func.name().ok_or(AnalyzerError::UnexpectedSyntax)?;
     let segments: Vec<&str> = name.split("::").collect();
@@ -12,21 +12,6 @@ pub(super) fn analyze_vector(ctx: &AnalyzerContext, func: &Function) -> Analyzer
         return Err(AnalyzerError::FunctionNotFound(name.to_string()));
     }
 
-    // Helper: for operations that take an array argument we expect the value to be of Kind::Array
-    // We assume vector functions work on arrays of numbers.
-    let expect_array = |arg: &surrealdb::sql::Value| -> AnalyzerResult<()> {
-        match ctx.resolve(arg)? {
-            Kind::Array(inner, _) => {
-                // Optionally: check that the inner type is a Number
-                if *inner != Kind::Number {
-                    return Err(AnalyzerError::UnexpectedSyntax);
-                }
-                Ok(())
-            }
-            _ => Err(AnalyzerError::UnexpectedSyntax),
-        }
-    };
-
     // Now choose based on the second segment
     match segments.get(1) {
         // Element-wise operations: add, subtract, multiply, divide, cross, project
@@ -41,8 +26,8 @@ pub(super) fn analyze_vector(ctx: &AnalyzerContext, func: &Function) -> Analyzer
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             // Check both arguments are arrays of numbers.
-            expect_array(&args[0])?;
-            expect_array(&args[1])?;
+            expect_array(ctx, &args[0])?;
+            expect_array(ctx, &args[1])?;
             // For these operations we return an array of numbers.
             Ok(Kind::Array(Box::new(Kind::Number), None))
         },
@@ -53,7 +38,7 @@ pub(super) fn analyze_vector(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 1 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            expect_array(&args[0])?;
+            expect_array(ctx, &args[0])?;
             Ok(Kind::Array(Box::new(Kind::Number), None))
         },
 
@@ -64,9 +49,9 @@ pub(super) fn analyze_vector(ctx: &AnalyzerContext, func: &Function) -> Analyzer
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
             // First argument must be an array.
-            expect_array(&args[0])?;
+            expect_array(ctx, &args[0])?;
             // Second argument must be a number.
-            match ctx.resolve(&args[1])? {
+            match ctx.resolve_as(&args[1], &Kind::Number)? {
                 Kind::Number => {},
                 _ => return Err(AnalyzerError::UnexpectedSyntax),
             }
@@ -79,8 +64,8 @@ pub(super) fn analyze_vector(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 2 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            expect_array(&args[0])?;
-            expect_array(&args[1])?;
+            expect_array(ctx, &args[0])?;
+            expect_array(ctx, &args[1])?;
             Ok(Kind::Number)
         },
 
@@ -90,8 +75,8 @@ pub(super) fn analyze_vector(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 2 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            expect_array(&args[0])?;
-            expect_array(&args[1])?;
+            expect_array(ctx, &args[0])?;
+            expect_array(ctx, &args[1])?;
             Ok(Kind::Number)
         },
 
@@ -101,7 +86,7 @@ pub(super) fn analyze_vector(ctx: &AnalyzerContext, func: &Function) -> Analyzer
             if args.len() != 1 {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
-            expect_array(&args[0])?;
+            expect_array(ctx, &args[0])?;
             Ok(Kind::Number)
         },
 
@@ -116,18 +101,18 @@ pub(super) fn analyze_vector(ctx: &AnalyzerContext, func: &Function) -> Analyzer
                     if args.len() != 2 {
                         return Err(AnalyzerError::UnexpectedSyntax);
                     }
-                    expect_array(&args[0])?;
-                    expect_array(&args[1])?;
+                    expect_array(ctx, &args[0])?;
+                    expect_array(ctx, &args[1])?;
                     Ok(Kind::Number)
                 },
                 Some(&"minkowski") => {
                     if args.len() != 3 {
                         return Err(AnalyzerError::UnexpectedSyntax);
                     }
-                    expect_array(&args[0])?;
-                    expect_array(&args[1])?;
+                    expect_array(ctx, &args[0])?;
+                    expect_array(ctx, &args[1])?;
                     // Third argument must be a number (the power parameter).
-                    match ctx.resolve(&args[2])? {
+                    match ctx.resolve_as(&args[2], &Kind::Number)? {
                         Kind::Number => {},
                         _ => return Err(AnalyzerError::UnexpectedSyntax),
                     }
@@ -154,8 +139,8 @@ pub(super) fn analyze_vector(ctx: &AnalyzerContext, func: &Function) -> Analyzer
                     if args.len() != 2 {
                         return Err(AnalyzerError::UnexpectedSyntax);
                     }
-                    expect_array(&args[0])?;
-                    expect_array(&args[1])?;
+                    expect_array(ctx, &args[0])?;
+                    expect_array(ctx, &args[1])?;
                     Ok(Kind::Number)
                 },
                 _ => Err(AnalyzerError::FunctionNotFound(name.to_string())),
@@ -167,3 +152,18 @@ pub(super) fn analyze_vector(ctx: &AnalyzerContext, func: &Function) -> Analyzer
         None => Err(AnalyzerError::UnexpectedSyntax),
     }
 }
+
+// Helper: for operations that take an array argument we expect the value to be of Kind::Array
+// We assume vector functions work on arrays of numbers.
+fn expect_array(ctx: &mut AnalyzerContext, arg: &surrealdb::sql::Value) -> AnalyzerResult<()> {
+    match ctx.resolve_as(arg, &Kind::Array(Box::new(Kind::Number), None))? {
+        Kind::Array(inner, _) => {
+            // Optionally: check that the inner type is a Number
+            if *inner != Kind::Number {
+                return Err(AnalyzerError::UnexpectedSyntax);
+            }
+            Ok(())
+        }
+        _ => Err(AnalyzerError::UnexpectedSyntax),
+    }
+}
@@ -0,0 +1,236 @@
+//! Resolution of idioms (field paths) to kinds.
+//!
+//! An idiom starts either from an explicit value (`$auth.name`, `(SELECT ..).id`)
+//! or from the current document bound to `$this` (`name`, `address.city`).
+//! Each part is then applied to the kind resolved so far:
+//!
+//! - Fields of a record are looked up in the table's field definitions, following
+//!   record links automatically
+//! - Fields of a literal object are looked up in the object
+//! - Accessing a field on an array maps the access over its elements
+//! - Accessing a field on an `option<T>` yields an optional result
+//!
+//! Paths into values of unknown shape resolve to `any`.
+use std::collections::BTreeMap;
+
+use surrealdb::sql::{Idiom, Kind, Literal, Part, Table};
+
+use super::context::AnalyzerContext;
+use super::error::{AnalyzerError, AnalyzerResult};
+use super::kind::union_of;
+
+pub fn resolve_idiom(ctx: &mut AnalyzerContext, idiom: &Idiom) -> AnalyzerResult<Kind> {
+    match idiom.0.split_first() {
+        Some((Part::Start(value), rest)) => {
+            let base = ctx.resolve(value)?;
+            resolve_parts(ctx, &base, rest)
+        }
+        Some(_) => match ctx.get_param("this").cloned() {
+            Some(document) => resolve_parts(ctx, &document, &idiom.0),
+            None => Ok(Kind::Any),
+        },
+        None => Ok(Kind::Any),
+    }
+}
+
+/// Applies the idiom `parts` to a value of kind `kind`.
+pub fn resolve_parts(
+    ctx: &mut AnalyzerContext,
+    kind: &Kind,
+    parts: &[Part],
+) -> AnalyzerResult<Kind> {
+    let Some((part, rest)) = parts.split_first() else {
+        return Ok(kind.clone());
+    };
+
+    match kind {
+        Kind::Any | Kind::Object => Ok(Kind::Any),
+        Kind::Option(inner) => {
+            let resolved = resolve_parts(ctx, inner, parts)?;
+            Ok(union_of([resolved, Kind::Null]))
+        }
+        Kind::Either(kinds) => {
+            let resolved = kinds
+                .iter()
+                .map(|kind| resolve_parts(ctx, kind, parts))
+                .collect::<AnalyzerResult<Vec<_>>>()?;
+            Ok(union_of(resolved))
+        }
+        Kind::Array(inner, len) | Kind::Set(inner, len) => match part {
+            Part::Index(_) | Part::First | Part::Last => resolve_parts(ctx, inner, rest),
+            // Filtering or selecting every element keeps the array as it is.
+            Part::All | Part::Where(_) => resolve_parts(ctx, kind, rest),
+            Part::Flatten => {
+                let flattened = match &**inner {
+                    Kind::Array(element, _) | Kind::Set(element, _) => {
+                        Kind::Array(element.clone(), None)
+                    }
+                    _ => Kind::Array(inner.clone(), *len),
+                };
+                resolve_parts(ctx, &flattened, rest)
+            }
+            _ => {
+                let resolved = resolve_parts(ctx, inner, parts)?;
+                Ok(Kind::Array(Box::new(resolved), None))
+            }
+        },
+        Kind::Literal(Literal::Array(items)) => match part {
+            Part::Index(index) => match items.get(index.as_usize()) {
+                Some(item) => resolve_parts(ctx, item, rest),
+                None => Ok(Kind::Null),
+            },
+            _ => {
+                let element = union_of(items.iter().cloned());
+                resolve_parts(ctx, &Kind::Array(Box::new(element), None), parts)
+            }
+        },
+        Kind::Record(tables) => resolve_record(ctx, tables, parts),
+        Kind::Literal(Literal::Object(fields)) => match part {
+            Part::Field(field) => match lookup_field(fields, parts) {
+                Some((kind, consumed)) => resolve_parts(ctx, kind, &parts[consumed..]),
+                // Records always carry their id, even though table types don't list it.
+                None if field.as_str() == "id" => {
+                    resolve_parts(ctx, &Kind::Record(Vec::new()), rest)
+                }
+                None => Err(AnalyzerError::field_not_found(
+                    field.to_string(),
+                    kind.to_string(),
+                )),
+            },
+            Part::All => resolve_parts(ctx, kind, rest),
+            _ => Ok(Kind::Any),
+        },
+        _ => match part {
+            Part::Field(field) => Err(AnalyzerError::field_not_found(
+                field.to_string(),
+                kind.to_string(),
+            )),
+            _ => Ok(Kind::Any),
+        },
+    }
+}
+
+/// Resolves a path on a record link, following the link to the table's schema.
+fn resolve_record(
+    ctx: &mut AnalyzerContext,
+    tables: &[Table],
+    parts: &[Part],
+) -> AnalyzerResult<Kind> {
+    // A link to any table, or to one of several tables, has no single schema.
+    let [table] = tables else {
+        return Ok(Kind::Any);
+    };
+
+    match &parts[0] {
+        Part::Field(field) => {
+            let fields: Vec<&str> = parts
+                .iter()
+                .map_while(|part| match part {
+                    Part::Field(field) => Some(field.as_str()),
+                    _ => None,
+                })
+                .collect();
+
+            // Fields may be defined at any depth (`address` or `address.city`),
+            // so try the longest defined path first. Fields hidden by their
+            // PERMISSIONS read as NONE.
+            for consumed in (1..=fields.len()).rev() {
+                let path = fields[..consumed].join(".");
+                let kind = ctx
+                    .get_field_definitions(&table.0)
+                    .into_iter()
+                    .find(|def| def.name.to_string() == path)
+                    .map(|def| match def.kind {
+                        Some(_) => ctx.visible_field_kind(def).unwrap_or(Kind::Null),
+                        None => Kind::Any,
+                    });
+                if let Some(kind) = kind {
+                    return resolve_parts(ctx, &kind, &parts[consumed..]);
+                }
+            }
+
+            if field.as_str() == "id" {
+                return resolve_parts(ctx, &Kind::Record(vec![table.clone()]), &parts[1..]);
+            }
+
+            Err(AnalyzerError::field_not_found(field.to_string(), &table.0))
+        }
+        Part::All => {
+            let full = ctx.build_full_table_type(&table.0)?;
+            resolve_parts(ctx, &full, &parts[1..])
+        }
+        _ => Ok(Kind::Any),
+    }
+}
+
+/// Finds the kind of the longest dotted key in `fields` matching the leading
+/// field parts, returning it with the number of parts consumed.
+fn lookup_field<'a>(
+    fields: &'a BTreeMap<String, Kind>,
+    parts: &[Part],
+) -> Option<(&'a Kind, usize)> {
+    let names: Vec<&str> = parts
+        .iter()
+        .map_while(|part| match part {
+            Part::Field(field) => Some(field.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    (1..=names.len()).rev().find_map(|consumed| {
+        fields
+            .get(&names[..consumed].join("."))
+            .map(|kind| (kind, consumed))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn document_paths() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD address ON user TYPE object;
+                DEFINE FIELD address.city ON user TYPE string;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD friends ON user TYPE array<record<user>>;
+                DEFINE FIELD nickname ON user TYPE option<string>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let stmt = "SELECT address.city AS city, friends.name AS friend_names, nickname FROM user;";
+        let analyzed_kind = analyze(&mut ctx, stmt).expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!(
+                "[array<{ city: string, friend_names: array<string>, nickname: option<string> }>]"
+            )
+        );
+    }
+
+    #[test]
+    fn unknown_field_in_path() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD friends ON user TYPE array<record<user>>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let err = analyze(&mut ctx, "SELECT friends.email AS emails FROM user;")
+            .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::FieldNotFound { .. }));
+    }
+}
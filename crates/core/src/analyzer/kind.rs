@@ -0,0 +1,266 @@
+//! Compatibility rules between [`Kind`]s.
+//!
+//! SurrealDB coerces values in a handful of well defined places: numbers widen,
+//! `option<T>` accepts NONE, generic records and objects accept any table or shape.
+//! The analyzer mirrors these rules so a resolved value can be checked against the
+//! kind expected by a function argument, an operator or a field definition.
+use surrealdb::sql::{statements::DefineStatement, Kind, Literal, Number, Statement};
+
+use super::error::{AnalyzerError, AnalyzerResult};
+
+pub trait KindExt {
+    /// Returns true if a value of kind `found` can be used where `self` is expected.
+    fn is_assignable_from(&self, found: &Kind) -> bool;
+
+    /// Returns true for `number`, `int`, `float`, `decimal` and numeric literals.
+    fn is_numeric(&self) -> bool;
+
+    /// Returns true if NONE is a valid value for this kind.
+    fn accepts_none(&self) -> bool;
+
+    /// Returns this kind with NONE removed, e.g. `option<string>` becomes `string`.
+    fn without_none(&self) -> Kind;
+}
+
+/// Parses a SurrealQL type, such as `option<string>` or `record<user>`.
+pub fn parse_kind(input: &str) -> AnalyzerResult<Kind> {
+    let query = format!("DEFINE FIELD placeholder ON placeholder TYPE {input}");
+    let statement = surrealdb::sql::parse(&query)
+        .map_err(AnalyzerError::Surreal)?
+        .into_iter()
+        .next();
+    match statement {
+        Some(Statement::Define(DefineStatement::Field(field))) => {
+            field.kind.ok_or(AnalyzerError::UnexpectedSyntax)
+        }
+        _ => Err(AnalyzerError::UnexpectedSyntax),
+    }
+}
+
+/// Builds the normalized union of `kinds`.
+///
+/// Nested unions are flattened and duplicates removed. A union containing `any`
+/// is `any`, a union with a single member is that member, and a union that may
+/// be NONE is wrapped in `option<..>`.
+pub fn union_of(kinds: impl IntoIterator<Item = Kind>) -> Kind {
+    fn flatten(kind: Kind, members: &mut Vec<Kind>, optional: &mut bool) {
+        match kind {
+            Kind::Either(kinds) => {
+                for kind in kinds {
+                    flatten(kind, members, optional);
+                }
+            }
+            Kind::Option(inner) => {
+                *optional = true;
+                flatten(*inner, members, optional);
+            }
+            Kind::Null => *optional = true,
+            kind => {
+                if !members.contains(&kind) {
+                    members.push(kind);
+                }
+            }
+        }
+    }
+
+    let mut members = Vec::new();
+    let mut optional = false;
+    for kind in kinds {
+        flatten(kind, &mut members, &mut optional);
+    }
+
+    if members.contains(&Kind::Any) {
+        return Kind::Any;
+    }
+
+    let union = match members.len() {
+        0 => return Kind::Null,
+        1 => members.remove(0),
+        _ => Kind::Either(members),
+    };
+
+    if optional {
+        Kind::Option(Box::new(union))
+    } else {
+        union
+    }
+}
+
+impl KindExt for Kind {
+    fn is_assignable_from(&self, found: &Kind) -> bool {
+        if self == found {
+            return true;
+        }
+
+        match (self, found) {
+            (Kind::Any, _) | (_, Kind::Any) => true,
+
+            // Every variant of a union must fit, and a union accepts any of its variants.
+            (expected, Kind::Either(found)) => found.iter().all(|k| expected.is_assignable_from(k)),
+            (Kind::Either(expected), found) => expected.iter().any(|k| k.is_assignable_from(found)),
+
+            // option<T> accepts NONE and anything T accepts.
+            (Kind::Option(_), Kind::Null) => true,
+            (Kind::Option(expected), Kind::Option(found)) => expected.is_assignable_from(found),
+            (Kind::Option(expected), found) => expected.is_assignable_from(found),
+
+            // Numbers of unknown precision fit anywhere a float or decimal fits, and
+            // integers widen losslessly to them. Only integers fit an int.
+            (Kind::Number, found) => found.is_numeric(),
+            (Kind::Int, Kind::Literal(Literal::Number(Number::Int(_)))) => true,
+            (Kind::Float, Kind::Number | Kind::Int | Kind::Literal(Literal::Number(_))) => true,
+            (
+                Kind::Decimal,
+                Kind::Number | Kind::Int | Kind::Float | Kind::Literal(Literal::Number(_)),
+            ) => true,
+
+            (Kind::String, Kind::Literal(Literal::String(_))) => true,
+            (Kind::Duration, Kind::Literal(Literal::Duration(_))) => true,
+
+            // A record without tables is a link to any table.
+            (Kind::Record(expected), Kind::Record(found)) => {
+                expected.is_empty()
+                    || found.is_empty()
+                    || found.iter().all(|table| expected.contains(table))
+            }
+
+            (Kind::Geometry(expected), Kind::Geometry(found)) => {
+                expected.is_empty() || found.iter().all(|geo| expected.contains(geo))
+            }
+            (Kind::Geometry(expected), Kind::Point) => {
+                expected.is_empty() || expected.iter().any(|geo| geo == "point")
+            }
+            (Kind::Point, Kind::Geometry(found)) => found.len() == 1 && found[0] == "point",
+
+            (
+                Kind::Array(expected, expected_len) | Kind::Set(expected, expected_len),
+                Kind::Array(found, found_len) | Kind::Set(found, found_len),
+            ) => {
+                let len_matches = match (expected_len, found_len) {
+                    (Some(expected), Some(found)) => expected == found,
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                };
+                len_matches && expected.is_assignable_from(found)
+            }
+            (
+                Kind::Array(expected, len) | Kind::Set(expected, len),
+                Kind::Literal(Literal::Array(items)),
+            ) => {
+                len.is_none_or(|len| len as usize == items.len())
+                    && items.iter().all(|item| expected.is_assignable_from(item))
+            }
+            (Kind::Literal(Literal::Array(expected)), Kind::Literal(Literal::Array(found))) => {
+                expected.len() == found.len()
+                    && expected
+                        .iter()
+                        .zip(found.iter())
+                        .all(|(expected, found)| expected.is_assignable_from(found))
+            }
+
+            (
+                Kind::Object,
+                Kind::Literal(Literal::Object(_)) | Kind::Literal(Literal::DiscriminatedObject(..)),
+            ) => true,
+            // An object of unknown shape may or may not fit; give it the benefit of the doubt.
+            (Kind::Literal(Literal::Object(_)), Kind::Object) => true,
+            (Kind::Literal(Literal::Object(expected)), Kind::Literal(Literal::Object(found))) => {
+                expected.iter().all(|(key, expected)| match found.get(key) {
+                    Some(found) => expected.is_assignable_from(found),
+                    None => expected.accepts_none(),
+                })
+            }
+
+            (Kind::Function(..), Kind::Function(..)) => true,
+
+            _ => false,
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Kind::Number
+                | Kind::Int
+                | Kind::Float
+                | Kind::Decimal
+                | Kind::Literal(Literal::Number(_))
+        )
+    }
+
+    fn accepts_none(&self) -> bool {
+        match self {
+            Kind::Any | Kind::Null | Kind::Option(_) => true,
+            Kind::Either(kinds) => kinds.iter().any(|k| k.accepts_none()),
+            _ => false,
+        }
+    }
+
+    fn without_none(&self) -> Kind {
+        match self {
+            Kind::Option(inner) => inner.without_none(),
+            Kind::Either(kinds) => union_of(
+                kinds
+                    .iter()
+                    .filter(|kind| **kind != Kind::Null)
+                    .map(|kind| kind.without_none()),
+            ),
+            kind => kind.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{union_of, KindExt};
+    use surrealdb::sql::{Kind, Literal, Number};
+    use surrealguard_macros::kind;
+
+    #[test]
+    fn numeric_widening() {
+        assert!(kind!("number").is_assignable_from(&kind!("int")));
+        assert!(kind!("float").is_assignable_from(&kind!("int")));
+        assert!(!kind!("int").is_assignable_from(&kind!("number")));
+        assert!(!kind!("int").is_assignable_from(&kind!("float")));
+
+        let literal = |number: Number| Kind::Literal(Literal::Number(number));
+        assert!(kind!("int").is_assignable_from(&literal(Number::Int(1))));
+        assert!(!kind!("int").is_assignable_from(&literal(Number::Float(1.5))));
+        assert!(kind!("float").is_assignable_from(&literal(Number::Int(1))));
+        assert!(!kind!("number").is_assignable_from(&kind!("string")));
+    }
+
+    #[test]
+    fn options_and_unions() {
+        assert!(kind!("option<string>").is_assignable_from(&kind!("string")));
+        assert!(kind!("option<string>").is_assignable_from(&kind!("null")));
+        assert!(!kind!("string").is_assignable_from(&kind!("option<string>")));
+        assert!(kind!("string | int").is_assignable_from(&kind!("int")));
+        assert!(!kind!("string").is_assignable_from(&kind!("string | int")));
+    }
+
+    #[test]
+    fn records_and_objects() {
+        assert!(kind!("record<user>").is_assignable_from(&kind!("record<user>")));
+        assert!(kind!("record").is_assignable_from(&kind!("record<user>")));
+        assert!(!kind!("record<user>").is_assignable_from(&kind!("record<post>")));
+        assert!(kind!("{ name: string, nick: option<string> }")
+            .is_assignable_from(&kind!("{ name: string }")));
+        assert!(!kind!("{ name: string }").is_assignable_from(&kind!("{ name: int }")));
+    }
+
+    #[test]
+    fn normalized_unions() {
+        assert_eq!(union_of([kind!("int"), kind!("int")]), kind!("int"));
+        assert_eq!(
+            union_of([kind!("string"), kind!("int | string")]),
+            kind!("string | int")
+        );
+        assert_eq!(
+            union_of([kind!("string"), kind!("null")]),
+            kind!("option<string>")
+        );
+        assert_eq!(union_of([kind!("string"), kind!("any")]), kind!("any"));
+        assert_eq!(kind!("option<string>").without_none(), kind!("string"));
+    }
+}
@@ -13,15 +13,21 @@
 //! - [`statements`]: Statement-specific analyzers for different query types (SELECT, CREATE, etc)
 //! - [`error`]: Error types specific to analysis failures
 //! - [`functions`]: Analysis of built-in and custom functions
+//! - [`expressions`]: Type inference for unary and binary operators
+//! - [`idiom`]: Resolution of field paths against records and objects
+//! - [`kind`]: Compatibility rules between kinds
 
 pub mod context;
 pub mod error;
+pub mod expressions;
 pub mod functions;
+pub mod idiom;
+pub mod kind;
 pub mod statements;
 
 use context::AnalyzerContext;
 use error::{AnalyzerError, AnalyzerResult};
-use surrealdb::sql::{Kind, Literal};
+use surrealdb::sql::{Kind, Literal, Statement};
 
 /// Analyzes a SurrealQL query string and returns the types of all statements.
 ///
@@ -30,8 +36,8 @@ use surrealdb::sql::{Kind, Literal};
 /// * `surql` - The SurrealQL query string to analyze
 ///
 /// # Returns
-/// A vector containing the result type of each statement in the query,
-/// or an error if analysis fails.
+/// A tuple (`Literal::Array`) containing the result type of each statement
+/// in the query, or an error if analysis fails.
 ///
 /// # Errors
 /// Returns an AnalyzerError if:
@@ -43,25 +49,105 @@ pub fn analyze(ctx: &mut AnalyzerContext, surql: &str) -> AnalyzerResult<Kind> {
     // Parse the query string into AST
     let statements = surrealdb::sql::parse(surql).map_err(AnalyzerError::Surreal)?;
 
-    // Analyze each statement
-    let kinds: Vec<Kind> = statements
-        .iter()
-        .map(|stmt| statements::analyze_statement(ctx, stmt))
-        .collect::<Result<Vec<_>, _>>()?;
-
-    match kinds.len() {
-        0 => Ok(Kind::Null),
-        //TODO: consolidate this after refactoring the kind! macro.
-        1 => Ok(Kind::Array(Box::new(kinds[0].clone()), None)),
-        _ => Ok(Kind::Literal(Literal::Array(kinds))),
+    // Analyze each statement. Parameters bound by the query only live
+    // as long as the query itself.
+    ctx.push_scope();
+    let kinds = analyze_statements(ctx, &statements);
+    ctx.pop_scope();
+
+    // SurrealDB responds with one result per statement, so the query
+    // evaluates to a tuple even when it holds a single statement.
+    Ok(Kind::Literal(Literal::Array(kinds?)))
+}
+
+/// The kind of a statement that responds with an error rather than a result,
+/// such as each statement of a cancelled transaction: `{ error: string }`.
+pub fn error_result() -> Kind {
+    Kind::Literal(Literal::Object(
+        [("error".to_string(), Kind::String)].into_iter().collect(),
+    ))
+}
+
+/// Returns true if `kind` is the kind of an error response, see [`error_result`].
+pub fn is_error_result(kind: &Kind) -> bool {
+    *kind == error_result()
+}
+
+/// The results of an open transaction.
+#[derive(Default)]
+struct Transaction {
+    results: Vec<Kind>,
+    /// Set once the transaction has returned early with a RETURN statement.
+    returned: bool,
+}
+
+/// Analyzes the statements of a query, collecting the results SurrealDB responds with.
+///
+/// BEGIN, COMMIT and CANCEL produce no results of their own. The statements of a
+/// transaction produce their results once it is committed, while a cancelled
+/// transaction produces an [`error_result`] for each of them instead. A RETURN
+/// within a transaction replaces its results with the returned value, and the
+/// statements after it aren't executed. So does an IF statement whose branches
+/// all RETURN. The results of a transaction that only may RETURN, such as one
+/// with `IF $done { RETURN 1 }`, are analyzed as if it doesn't.
+fn analyze_statements(
+    ctx: &mut AnalyzerContext,
+    statements: &[Statement],
+) -> AnalyzerResult<Vec<Kind>> {
+    let mut results = Vec::new();
+    let mut transaction: Option<Transaction> = None;
+
+    for stmt in statements {
+        match stmt {
+            Statement::Begin(_) => {
+                if transaction.is_some() {
+                    return Err(AnalyzerError::InvalidTransaction(
+                        "BEGIN within an open transaction".into(),
+                    ));
+                }
+                transaction = Some(Transaction::default());
+            }
+            Statement::Commit(_) => {
+                let committed = transaction.take().ok_or_else(|| {
+                    AnalyzerError::InvalidTransaction("COMMIT without a matching BEGIN".into())
+                })?;
+                results.extend(committed.results);
+            }
+            Statement::Cancel(_) => {
+                let cancelled = transaction.take().ok_or_else(|| {
+                    AnalyzerError::InvalidTransaction("CANCEL without a matching BEGIN".into())
+                })?;
+                results.extend(cancelled.results.iter().map(|_| error_result()));
+            }
+            stmt => {
+                let kind = statements::analyze_statement(ctx, stmt)?;
+                match transaction.as_mut() {
+                    None => results.push(kind),
+                    Some(open) if open.returned => {}
+                    Some(open) if statements::logic::always_returns(stmt) => {
+                        open.results = vec![kind];
+                        open.returned = true;
+                    }
+                    Some(open) => open.results.push(kind),
+                }
+            }
+        }
     }
+
+    if transaction.is_some() {
+        return Err(AnalyzerError::InvalidTransaction(
+            "BEGIN without a matching COMMIT or CANCEL".into(),
+        ));
+    }
+
+    Ok(results)
 }
 
 #[cfg(test)]
 mod test {
     use surrealguard_macros::kind;
 
-    use crate::analyzer::{analyze, context::AnalyzerContext};
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
 
     #[test]
     fn multiple_statements() {
@@ -113,4 +199,131 @@ mod test {
 
         assert_eq!(analyzed_kind, expected_kind);
     }
+
+    #[test]
+    fn committed_transaction() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            BEGIN TRANSACTION;
+            CREATE user SET name = 'Jane';
+            SELECT name FROM user;
+            COMMIT TRANSACTION;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[array<{ name: string }>, array<{ name: string }>]")
+        );
+    }
+
+    #[test]
+    fn cancelled_transaction() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            RETURN 1;
+            BEGIN;
+            CREATE user SET name = 'Jane';
+            CANCEL;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[int, { error: string }]"));
+    }
+
+    #[test]
+    fn return_within_transaction() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            BEGIN;
+            LET $user = (CREATE ONLY user SET name = 'Jane');
+            RETURN $user.name;
+            SELECT * FROM user;
+            COMMIT;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[string]"));
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            BEGIN;
+            IF $done { RETURN 'done' } ELSE { RETURN 1 };
+            SELECT * FROM user;
+            COMMIT;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[string | int]"));
+
+        // Transactions that may not RETURN keep every result.
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            BEGIN;
+            IF $done { RETURN 'done' };
+            SELECT * FROM user;
+            COMMIT;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[option<string>, array<{ name: string }>]"));
+    }
+
+    #[test]
+    fn mismatched_transactions() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        for query in [
+            "BEGIN; SELECT * FROM user;",
+            "SELECT * FROM user; COMMIT;",
+            "CANCEL;",
+            "BEGIN; BEGIN; COMMIT; COMMIT;",
+        ] {
+            let err = analyze(&mut ctx, query).expect_err("Analysis should fail");
+            assert!(matches!(err, AnalyzerError::InvalidTransaction(_)));
+        }
+    }
 }
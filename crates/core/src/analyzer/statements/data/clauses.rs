@@ -0,0 +1,350 @@
+use std::collections::BTreeMap;
+
+use surrealdb::sql::{
+    from_value, statements::SelectStatement, to_value, Idiom, Kind, Literal, Order, Part, Table,
+    Value, With,
+};
+
+use crate::analyzer::{
+    context::{AnalyzerContext, DefinitionKey},
+    error::{AnalyzerError, AnalyzerResult},
+    idiom::resolve_parts,
+    kind::{union_of, KindExt},
+};
+
+/// Analyzes the ORDER, LIMIT, START, SPLIT and WITH clauses of a SELECT, given
+/// the kind its projections produce.
+///
+/// - ORDER BY names a projected field, or a field of the table selected from,
+///   and COLLATE and NUMERIC only order strings
+/// - LIMIT and START take integers, and infer the kind of parameters passed
+/// - SPLIT returns a row for each element of an array field, and that field then
+///   holds a single element
+/// - WITH INDEX names indexes defined on the table
+///
+/// TIMEOUT always holds a duration, so it needs no checking.
+pub(super) fn analyze_clauses(
+    ctx: &mut AnalyzerContext,
+    stmt: &SelectStatement,
+    kind: Kind,
+) -> AnalyzerResult<Kind> {
+    let table = source_table(stmt);
+
+    // Values selected with SELECT VALUE have no fields to order by.
+    if !stmt.expr.1 {
+        let row = row_kind(&kind, stmt.only);
+        for order in orders(stmt)? {
+            let ordered = resolve_order(ctx, &row, table.as_deref(), &order.value)?;
+            let value = ordered.without_none();
+            if (order.collate || order.numeric)
+                && value != Kind::Any
+                && !Kind::String.is_assignable_from(&value)
+            {
+                return Err(AnalyzerError::TypeMismatch {
+                    expected: Kind::String.to_string(),
+                    found: ordered.to_string(),
+                    table: table.clone(),
+                    field: Some(order.value.to_string()),
+                });
+            }
+        }
+    }
+
+    for (clause, value) in [
+        ("LIMIT", stmt.limit.as_ref().map(|limit| &limit.0)),
+        ("START", stmt.start.as_ref().map(|start| &start.0)),
+    ] {
+        let Some(value) = value else {
+            continue;
+        };
+        let found = ctx.resolve_as(value, &Kind::Int)?;
+        if !Kind::Int.is_assignable_from(&found) {
+            return Err(AnalyzerError::TypeMismatch {
+                expected: Kind::Int.to_string(),
+                found: found.to_string(),
+                table: None,
+                field: Some(clause.to_string()),
+            });
+        }
+    }
+
+    if let Some(With::Index(names)) = &stmt.with {
+        let Some(table) = table.as_deref() else {
+            return Err(AnalyzerError::schema_violation(
+                "WITH INDEX needs a table to select from",
+                None::<String>,
+                None::<String>,
+            ));
+        };
+        for name in names {
+            if !ctx
+                .get_indexes(table)
+                .iter()
+                .any(|index| index.name == *name)
+            {
+                let key = DefinitionKey::Index(table.to_string(), name.to_string());
+                return Err(AnalyzerError::DefinitionNotFound(key.to_string()));
+            }
+        }
+    }
+
+    let Some(splits) = &stmt.split else {
+        return Ok(kind);
+    };
+    let mut kind = kind;
+    for split in splits.0.iter() {
+        kind = map_rows(kind, stmt.only, |row| split_field(row, &split.0))?;
+    }
+    Ok(kind)
+}
+
+/// The table a SELECT reads from, if it reads from a table or record.
+fn source_table(stmt: &SelectStatement) -> Option<String> {
+    match stmt.what.0.first()? {
+        Value::Table(table) => Some(table.0.clone()),
+        Value::Thing(thing) => Some(thing.tb.clone()),
+        _ => None,
+    }
+}
+
+/// The fields a SELECT is ordered by, or none if it's unordered or ordered by
+/// RAND(). surrealdb doesn't export the ORDER clause's type, so it's read back
+/// from its serialized form, `{ Order: [order, ...] }`.
+fn orders(stmt: &SelectStatement) -> AnalyzerResult<Vec<Order>> {
+    let Some(ordering) = &stmt.order else {
+        return Ok(Vec::new());
+    };
+    let unsupported =
+        || AnalyzerError::Unimplemented(format!("Unsupported {}", ordering.to_string().trim()));
+    match to_value(ordering.clone()).map_err(|_| unsupported())? {
+        Value::Strand(random) if random.as_str() == "Random" => Ok(Vec::new()),
+        Value::Object(ordering) => match ordering.get("Order") {
+            Some(Value::Array(list)) => list
+                .iter()
+                .map(|order| from_value(order.clone()).map_err(|_| unsupported()))
+                .collect(),
+            _ => Err(unsupported()),
+        },
+        _ => Err(unsupported()),
+    }
+}
+
+/// The kind of each row of a SELECT's result.
+fn row_kind(kind: &Kind, only: bool) -> Kind {
+    match kind {
+        _ if only => kind.clone(),
+        Kind::Array(inner, _) | Kind::Set(inner, _) => (**inner).clone(),
+        Kind::Literal(Literal::Array(items)) => union_of(items.iter().cloned()),
+        _ => Kind::Any,
+    }
+}
+
+/// Resolves an ORDER BY idiom on a projected row, falling back to the fields
+/// of the table selected from.
+fn resolve_order(
+    ctx: &mut AnalyzerContext,
+    row: &Kind,
+    table: Option<&str>,
+    idiom: &Idiom,
+) -> AnalyzerResult<Kind> {
+    match (resolve_parts(ctx, row, &idiom.0), table) {
+        (Err(AnalyzerError::FieldNotFound { .. }), Some(table)) => {
+            let document = Kind::Record(vec![Table::from(table)]);
+            ctx.resolve_in_document(&document, &Value::Idiom(idiom.clone()))
+        }
+        (resolved, _) => resolved,
+    }
+}
+
+/// Applies `f` to each row of a SELECT's result. Rows may be split, so the
+/// number of rows is no longer known.
+fn map_rows(
+    kind: Kind,
+    only: bool,
+    f: impl Fn(Kind) -> AnalyzerResult<Kind>,
+) -> AnalyzerResult<Kind> {
+    match kind {
+        _ if only => f(kind),
+        Kind::Array(inner, _) => Ok(Kind::Array(Box::new(f(*inner)?), None)),
+        Kind::Literal(Literal::Array(items)) => {
+            let items = items.into_iter().map(f).collect::<AnalyzerResult<_>>()?;
+            Ok(Kind::Literal(Literal::Array(items)))
+        }
+        kind => Ok(kind),
+    }
+}
+
+/// Replaces the array field at `path` of a row with the kind of its elements.
+fn split_field(row: Kind, path: &[Part]) -> AnalyzerResult<Kind> {
+    let Kind::Literal(Literal::Object(mut fields)) = row else {
+        return Ok(row);
+    };
+
+    // Projected fields may be named by their whole path (`address.tags`).
+    let key = Idiom::from(path.to_vec()).to_string();
+    if let Some(kind) = fields.remove(&key) {
+        fields.insert(key, element_kind(kind));
+        return Ok(Kind::Literal(Literal::Object(fields)));
+    }
+
+    match path.split_first() {
+        Some((Part::Field(field), rest)) if !rest.is_empty() => {
+            let nested = fields
+                .remove(field.as_str())
+                .ok_or_else(|| split_not_found(&key, &fields))?;
+            fields.insert(field.to_string(), split_field(nested, rest)?);
+            Ok(Kind::Literal(Literal::Object(fields)))
+        }
+        _ => Err(split_not_found(&key, &fields)),
+    }
+}
+
+/// The kind of a field holding one element of an array once the row is split.
+/// Values other than arrays are left as they are.
+fn element_kind(kind: Kind) -> Kind {
+    match kind {
+        Kind::Array(inner, _) | Kind::Set(inner, _) => *inner,
+        Kind::Literal(Literal::Array(items)) => union_of(items),
+        Kind::Option(inner) => union_of([element_kind(*inner), Kind::Null]),
+        kind => kind,
+    }
+}
+
+fn split_not_found(field: &str, fields: &BTreeMap<String, Kind>) -> AnalyzerError {
+    AnalyzerError::field_not_found(
+        field,
+        Kind::Literal(Literal::Object(fields.clone())).to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn ordering() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            r#"
+            SELECT name FROM user ORDER BY name COLLATE ASC;
+            SELECT name AS title, age FROM user ORDER BY title NUMERIC, age DESC;
+            SELECT * FROM user ORDER BY RAND();
+        "#,
+        )
+        .expect("Analysis should succeed");
+
+        let err = analyze(&mut ctx, "SELECT * FROM user ORDER BY email NUMERIC;")
+            .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::FieldNotFound { .. }));
+
+        // COLLATE and NUMERIC only order strings.
+        for stmt in [
+            "SELECT age FROM user ORDER BY age NUMERIC;",
+            "SELECT * FROM user ORDER BY age COLLATE;",
+        ] {
+            let err = analyze(&mut ctx, stmt).expect_err("Analysis should fail");
+            assert!(
+                matches!(err, AnalyzerError::TypeMismatch { .. }),
+                "{stmt} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn limit_and_start() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            "SELECT name FROM user LIMIT $limit START $start TIMEOUT 5s;",
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            ctx.get_all_inferred_params(),
+            [
+                ("limit".to_string(), kind!("int")),
+                ("start".to_string(), kind!("int")),
+            ]
+        );
+
+        let err = analyze(&mut ctx, "SELECT name FROM user LIMIT 'ten';")
+            .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn split() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD tags ON user TYPE array<string>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(&mut ctx, "SELECT name, tags FROM user SPLIT tags;")
+            .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[array<{ name: string, tags: string }>]")
+        );
+
+        let err =
+            analyze(&mut ctx, "SELECT * FROM user SPLIT email;").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::FieldNotFound { .. }));
+    }
+
+    #[test]
+    fn with_index() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+            DEFINE INDEX name ON user FIELDS name;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            r#"
+            SELECT name FROM user WITH INDEX name WHERE name = 'Tobie';
+            SELECT name FROM user WITH NOINDEX WHERE name = 'Tobie';
+        "#,
+        )
+        .expect("Analysis should succeed");
+
+        let err = analyze(
+            &mut ctx,
+            "SELECT name FROM user WITH INDEX email WHERE name = 'Tobie';",
+        )
+        .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::DefinitionNotFound(_)));
+    }
+}
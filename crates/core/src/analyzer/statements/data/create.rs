@@ -1,8 +1,9 @@
+use super::fields::{analyze_data, Write};
 use crate::analyzer::{
     context::AnalyzerContext,
     error::{AnalyzerError, AnalyzerResult},
 };
-use surrealdb::sql::{statements::CreateStatement, Data, Idiom, Kind, Value};
+use surrealdb::sql::{statements::CreateStatement, Kind};
 
 /// Analyzes a CREATE statement such as:
 ///
@@ -26,46 +27,8 @@ pub fn analyze_create(ctx: &mut AnalyzerContext, stmt: &CreateStatement) -> Anal
         _ => return Err(AnalyzerError::UnexpectedSyntax),
     };
 
-    // Analyze the Data variant for parameter inference
-    if let Some(data) = &stmt.data {
-        match data {
-            Data::ContentExpression(value) => {
-                match value {
-                    Value::Param(param_name) => {
-                        // For CREATE ... CONTENT $record
-                        ctx.infer_param_from_table(&table_name, param_name)?;
-                    }
-                    Value::Object(obj) => {
-                        // For CREATE ... CONTENT { field: $param }
-                        for (field, value) in obj.iter() {
-                            if let Value::Param(param_name) = value {
-                                let field_idiom = Idiom::from(field.clone());
-                                ctx.infer_param_from_field(&table_name, &field_idiom, param_name)?;
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-            Data::SingleExpression(value) => {
-                // Handle single value expressions
-                if let Value::Param(param_name) = value {
-                    ctx.infer_param_from_table(&table_name, param_name)?;
-                }
-            }
-            Data::ValuesExpression(values) => {
-                // Handle VALUES (...) syntax
-                for value_set in values {
-                    for (idiom, value) in value_set {
-                        if let Value::Param(param_name) = value {
-                            ctx.infer_param_from_field(&table_name, idiom, param_name)?;
-                        }
-                    }
-                }
-            }
-            _ => {}
-        }
-    }
+    // Infer parameters from, and check values against, the fields they're written to.
+    analyze_data(ctx, &table_name, stmt.data.as_ref(), Write::Create)?;
 
     let target_type = ctx.build_full_table_type(&table_name)?;
 
@@ -98,7 +61,7 @@ mod tests {
         let stmt = "CREATE user CONTENT { name: 'John', age: 42 };";
         let analyzed_kind = analyze(&mut ctx, stmt).expect("Analysis should succeed");
 
-        let expected_kind = kind!("array<array<{ name: string, age: number }>>");
+        let expected_kind = kind!("[array<{ name: string, age: number }>]");
         assert_eq!(analyzed_kind, expected_kind);
     }
 
@@ -165,9 +128,9 @@ mod tests {
             let stmt = "CREATE ONLY user CONTENT { name: 'John', age: 42 };";
             let analyzed_kind = analyze(&mut ctx, stmt).expect("Analysis should succeed");
 
-            //The array is because 'analyze' wraps each statement in the array.
-            //This is the behavior of SurrealDB if there are multiple statements.
-            let expected_kind = kind!("array<{ name: string, age: number }>");
+            //The tuple is because 'analyze' returns the result of each statement.
+            //This is the behavior of SurrealDB for every query.
+            let expected_kind = kind!("[{ name: string, age: number }]");
             assert_eq!(analyzed_kind, expected_kind);
         }
 }
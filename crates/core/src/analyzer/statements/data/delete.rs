@@ -1,10 +1,19 @@
+use super::fields::check_not_view;
 use crate::analyzer::{context::AnalyzerContext, error::AnalyzerResult};
-use surrealdb::sql::{statements::DeleteStatement, Kind, Literal};
+use surrealdb::sql::{statements::DeleteStatement, Kind, Literal, Value};
 
 /// Analyzes a DELETE statement.
 ///
-/// As per our design DELETE always returns an empty array.
-pub fn analyze_delete(_ctx: &mut AnalyzerContext, _stmt: &DeleteStatement) -> AnalyzerResult<Kind> {
+/// Records of views can't be deleted. As per our design DELETE always returns
+/// an empty array.
+pub fn analyze_delete(ctx: &mut AnalyzerContext, stmt: &DeleteStatement) -> AnalyzerResult<Kind> {
+    for target in stmt.what.iter() {
+        match target {
+            Value::Table(table) => check_not_view(ctx, table)?,
+            Value::Thing(thing) => check_not_view(ctx, &thing.tb)?,
+            _ => {}
+        }
+    }
     Ok(Kind::Literal(Literal::Array(vec![])))
 }
 
@@ -29,7 +38,7 @@ mod tests {
         let stmt = "DELETE user WHERE name = 'Jane';";
         let analyzed_kind = analyze(&mut ctx, stmt).expect("Analysis should succeed");
 
-        let expected_kind = kind!("array<[]>");
+        let expected_kind = kind!("[[]]");
         assert_eq!(analyzed_kind, expected_kind);
     }
 }
@@ -0,0 +1,798 @@
+//! Analysis of the data clauses (`SET`, `CONTENT`, `MERGE`, ...) of write statements.
+//!
+//! Input parameters are inferred from the field, or the whole table, they are
+//! written to. Every other value is resolved against the record being written
+//! and checked against the kind of its field. Object literals written to a
+//! field with nested field definitions are checked field by field.
+//!
+//! Writes to SCHEMAFULL tables are checked the way the database checks them:
+//! every written field must be defined (or sit inside a FLEXIBLE object), and
+//! a record that is created or replaced must provide every required field.
+//! SCHEMALESS tables accept any field.
+//!
+//! Writes the current access level isn't permitted to make, on the table or
+//! on any of the written fields, are denied.
+use surrealdb::sql::{
+    statements::DefineStatement, Data, Idiom, Kind, Object, Operator, Part, Table, Value,
+};
+
+use crate::analyzer::{
+    context::{Action, AnalyzerContext, Grant},
+    error::{AnalyzerError, AnalyzerResult},
+    kind::KindExt,
+};
+
+/// Checks that `table` isn't a view, whose records only the database writes.
+pub(super) fn check_not_view(ctx: &AnalyzerContext, table: &str) -> AnalyzerResult<()> {
+    match ctx.is_view(table) {
+        true => Err(AnalyzerError::schema_violation(
+            "Table is a view and can't be written to",
+            Some(table),
+            None::<String>,
+        )),
+        false => Ok(()),
+    }
+}
+
+/// The kind of write a data clause belongs to.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum Write {
+    /// CREATE, INSERT and RELATE, which create the record.
+    Create,
+    /// UPDATE and ON DUPLICATE KEY UPDATE, which change an existing record.
+    Update,
+    /// UPSERT, which may do either.
+    Upsert,
+}
+
+/// Analyzes the data written to `table` by a CREATE, UPDATE, UPSERT, INSERT or
+/// RELATE statement.
+///
+/// Created records must provide their required fields, even when there is no
+/// data at all. Updated records can't change their READONLY fields.
+pub(super) fn analyze_data(
+    ctx: &mut AnalyzerContext,
+    table: &str,
+    data: Option<&Data>,
+    write: Write,
+) -> AnalyzerResult<()> {
+    check_not_view(ctx, table)?;
+
+    let target = Target {
+        table,
+        schemafull: matches!(
+            ctx.find_table_definition(table),
+            Some(DefineStatement::Table(table_def)) if table_def.full
+        ),
+        write,
+    };
+
+    target.check_permitted(ctx, None)?;
+
+    let Some(data) = data else {
+        return match write {
+            Write::Create => target.check_required(ctx, &[], &[]),
+            _ => Ok(()),
+        };
+    };
+
+    match data {
+        Data::SetExpression(sets) | Data::UpdateExpression(sets) => {
+            for (idiom, op, value) in sets {
+                target.check_defined(ctx, idiom)?;
+                target.check_permitted(ctx, Some(idiom))?;
+                target.check_writable(ctx, idiom)?;
+                match op {
+                    Operator::Equal => target.check_field(ctx, idiom, value, true)?,
+                    // `+=` and `-=` combine the value with the current one.
+                    _ => match value {
+                        Value::Param(param) if ctx.is_input_param(value) => {
+                            ctx.infer_param_from_field(table, idiom, param)?
+                        }
+                        _ => target.check_combined(ctx, idiom, value)?,
+                    },
+                }
+            }
+            if write == Write::Create {
+                let provided: Vec<Idiom> = sets.iter().map(|(idiom, _, _)| idiom.clone()).collect();
+                target.check_required(ctx, &[], &provided)?;
+            }
+        }
+        // Merged (and patched) records keep the fields they don't mention.
+        Data::MergeExpression(value) | Data::PatchExpression(value) => {
+            target.check_record(ctx, value, false)?
+        }
+        Data::ContentExpression(value)
+        | Data::ReplaceExpression(value)
+        | Data::SingleExpression(value) => target.check_record(ctx, value, true)?,
+        Data::ValuesExpression(rows) => {
+            for row in rows {
+                for (idiom, value) in row {
+                    target.check_defined(ctx, idiom)?;
+                    target.check_permitted(ctx, Some(idiom))?;
+                    target.check_field(ctx, idiom, value, true)?;
+                }
+                let provided: Vec<Idiom> = row.iter().map(|(idiom, _)| idiom.clone()).collect();
+                target.check_required(ctx, &[], &provided)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// The table a data clause writes to.
+struct Target<'a> {
+    table: &'a str,
+    schemafull: bool,
+    write: Write,
+}
+
+impl Target<'_> {
+    /// The record being written, which values are evaluated against.
+    fn document(&self) -> Kind {
+        Kind::Record(vec![Table::from(self.table)])
+    }
+
+    /// Checks a whole record, or an array of them for INSERT. A `complete`
+    /// record replaces the stored one, so it must provide every required field.
+    ///
+    /// Parameters are inferred as the table's create type for complete records,
+    /// and as its patch type for merged ones.
+    fn check_record(
+        &self,
+        ctx: &mut AnalyzerContext,
+        value: &Value,
+        complete: bool,
+    ) -> AnalyzerResult<()> {
+        match value {
+            Value::Param(_) if ctx.is_input_param(value) => {
+                let kind = match complete {
+                    true => ctx.build_create_type(self.table)?,
+                    false => ctx.build_patch_type(self.table)?,
+                };
+                ctx.infer_param(value, &kind, self.table).map(|_| ())
+            }
+            Value::Object(object) => self.check_object(ctx, &[], object, complete),
+            Value::Array(records) => {
+                for record in records.iter() {
+                    self.check_record(ctx, record, complete)?;
+                }
+                Ok(())
+            }
+            _ => ctx.resolve_in_document(&self.document(), value).map(|_| ()),
+        }
+    }
+
+    /// Checks each member of `object`, written to the field at `parent` (or to
+    /// the record itself when `parent` is empty).
+    fn check_object(
+        &self,
+        ctx: &mut AnalyzerContext,
+        parent: &[Part],
+        object: &Object,
+        complete: bool,
+    ) -> AnalyzerResult<()> {
+        for (key, value) in object.iter() {
+            let field = Idiom::from([parent, &[Part::from(key.clone())]].concat());
+            self.check_defined(ctx, &field)?;
+            self.check_permitted(ctx, Some(&field))?;
+            if !complete {
+                self.check_writable(ctx, &field)?;
+            }
+            self.check_field(ctx, &field, value, complete)?;
+        }
+        if complete {
+            let provided: Vec<Idiom> = object
+                .keys()
+                .map(|key| Idiom::from([parent, &[Part::from(key.clone())]].concat()))
+                .collect();
+            self.check_required(ctx, parent, &provided)?;
+        }
+        Ok(())
+    }
+
+    /// Checks a value written to `field`.
+    fn check_field(
+        &self,
+        ctx: &mut AnalyzerContext,
+        field: &Idiom,
+        value: &Value,
+        complete: bool,
+    ) -> AnalyzerResult<()> {
+        match value {
+            Value::Param(param) if ctx.is_input_param(value) => {
+                return ctx.infer_param_from_field(self.table, field, param);
+            }
+            Value::Object(object) if has_nested_fields(ctx, self.table, field) => {
+                return self.check_object(ctx, &field.0, object, complete);
+            }
+            _ => {}
+        }
+
+        let found = ctx.resolve_in_document(&self.document(), value)?;
+        let expected = self.field_kind(ctx, field);
+
+        match expected {
+            Some(expected) if !expected.is_assignable_from(&found) => {
+                Err(AnalyzerError::field_type_mismatch(
+                    self.table,
+                    field.to_string(),
+                    &expected,
+                    &found,
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks a value combined with `field` by `+=` or `-=`. Arrays take one of
+    /// their elements or another array, and other fields a value of their kind.
+    fn check_combined(
+        &self,
+        ctx: &mut AnalyzerContext,
+        field: &Idiom,
+        value: &Value,
+    ) -> AnalyzerResult<()> {
+        let found = ctx.resolve_in_document(&self.document(), value)?;
+        let Some(expected) = self.field_kind(ctx, field) else {
+            return Ok(());
+        };
+        let accepted = match expected.without_none() {
+            Kind::Array(inner, _) | Kind::Set(inner, _) => {
+                inner.is_assignable_from(&found) || expected.is_assignable_from(&found)
+            }
+            _ => expected.is_assignable_from(&found),
+        };
+        match accepted {
+            true => Ok(()),
+            false => Err(AnalyzerError::field_type_mismatch(
+                self.table,
+                field.to_string(),
+                &expected,
+                &found,
+            )),
+        }
+    }
+
+    /// The kind `field` is defined with, if it is defined with one.
+    fn field_kind(&self, ctx: &AnalyzerContext, field: &Idiom) -> Option<Kind> {
+        ctx.get_field_definitions(self.table)
+            .into_iter()
+            .find(|def| def.name == *field)
+            .and_then(|def| def.kind.clone())
+    }
+
+    /// Checks that a SCHEMAFULL table can store `field`.
+    ///
+    /// A field is storable if it is defined, or if the closest defined field
+    /// above it is FLEXIBLE or holds something other than a plain object.
+    /// Paths into arrays and the like are left to type checking.
+    fn check_defined(&self, ctx: &AnalyzerContext, field: &Idiom) -> AnalyzerResult<()> {
+        if !self.schemafull || !field.0.iter().all(|part| matches!(part, Part::Field(_))) {
+            return Ok(());
+        }
+        if let [Part::Field(id)] = field.0.as_slice() {
+            if id.as_str() == "id" {
+                return Ok(());
+            }
+        }
+
+        let definitions = ctx.get_field_definitions(self.table);
+        let closest = (1..=field.0.len())
+            .rev()
+            .find_map(|len| definitions.iter().find(|def| def.name.0 == field.0[..len]));
+        let defined = match closest {
+            Some(def) if def.name == *field => true,
+            Some(def) => {
+                def.flex
+                    || !matches!(
+                        def.kind.as_ref().map(KindExt::without_none),
+                        Some(Kind::Object)
+                    )
+            }
+            None => false,
+        };
+
+        match defined {
+            true => Ok(()),
+            false => Err(AnalyzerError::schema_violation(
+                "Field is not defined on this SCHEMAFULL table",
+                Some(self.table),
+                Some(field.to_string()),
+            )),
+        }
+    }
+
+    /// Checks that the current access level may write to the table, or to
+    /// `field` when given. An UPSERT needs permission to create or to update.
+    fn check_permitted(&self, ctx: &AnalyzerContext, field: Option<&Idiom>) -> AnalyzerResult<()> {
+        let (actions, message): (&[Action], _) = match self.write {
+            Write::Create => (&[Action::Create], "Records can't be created"),
+            Write::Update => (&[Action::Update], "Records can't be updated"),
+            Write::Upsert => (
+                &[Action::Create, Action::Update],
+                "Records can't be created or updated",
+            ),
+        };
+        let denied = actions.iter().all(|&action| {
+            let grant = match field {
+                Some(field) => ctx.field_grant(self.table, field, action),
+                None => ctx.table_grant(self.table, action),
+            };
+            grant == Grant::Denied
+        });
+
+        match (denied, field) {
+            (false, _) => Ok(()),
+            (true, Some(field)) => Err(AnalyzerError::permission_denied(
+                message,
+                format!("{}.{}", self.table, field),
+            )),
+            (true, None) => Err(AnalyzerError::permission_denied(message, self.table)),
+        }
+    }
+
+    /// Checks that an UPDATE or UPSERT doesn't change `field`, or any field
+    /// containing it, when it is READONLY. An UPSERT may update a record that
+    /// already exists.
+    fn check_writable(&self, ctx: &AnalyzerContext, field: &Idiom) -> AnalyzerResult<()> {
+        if self.write == Write::Create {
+            return Ok(());
+        }
+
+        let readonly = ctx
+            .get_field_definitions(self.table)
+            .into_iter()
+            .find(|def| def.readonly && field.0.starts_with(&def.name.0));
+        match readonly {
+            Some(def) => Err(AnalyzerError::schema_violation(
+                "Field is READONLY and can't be updated",
+                Some(self.table),
+                Some(def.name.to_string()),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that the fields `provided` under `parent` include every required
+    /// field there: fields whose kind doesn't accept NONE and that have
+    /// neither a DEFAULT nor a VALUE clause.
+    ///
+    /// `provided` holds the full path of every written field. Writing a field
+    /// provides the fields containing it as well as those within it, and a
+    /// nested field is only required once something writes the field holding
+    /// it, so `SET address.city = 'London'` must also set a required
+    /// `address.zip`.
+    fn check_required(
+        &self,
+        ctx: &AnalyzerContext,
+        parent: &[Part],
+        provided: &[Idiom],
+    ) -> AnalyzerResult<()> {
+        if !self.schemafull {
+            return Ok(());
+        }
+
+        for def in ctx.get_field_definitions(self.table) {
+            let name = &def.name.0;
+            if name.len() <= parent.len() || !name.starts_with(parent) {
+                continue;
+            }
+            let Some(Part::Field(key)) = name.last() else {
+                continue;
+            };
+            // Record ids and relation endpoints are set by the statement itself.
+            if name.len() == 1 && ["id", "in", "out"].contains(&key.as_str()) {
+                continue;
+            }
+            if provided
+                .iter()
+                .any(|path| name.starts_with(path) || path.starts_with(name))
+            {
+                continue;
+            }
+            let container = &name[..name.len() - 1];
+            if container.len() > parent.len()
+                && !provided.iter().any(|path| path.starts_with(container))
+            {
+                continue;
+            }
+            if def.default.is_some() || def.value.is_some() {
+                continue;
+            }
+            if def.kind.as_ref().is_some_and(|kind| !kind.accepts_none()) {
+                return Err(AnalyzerError::schema_violation(
+                    "Required field is missing",
+                    Some(self.table),
+                    Some(def.name.to_string()),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if fields are defined below `field`, e.g. `address.city` below `address`.
+fn has_nested_fields(ctx: &AnalyzerContext, table: &str, field: &Idiom) -> bool {
+    ctx.get_field_definitions(table)
+        .iter()
+        .any(|def| def.name.0.len() > field.0.len() && def.name.0.starts_with(&field.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{
+        analyze,
+        context::{AnalyzerContext, Role},
+        error::AnalyzerError,
+    };
+
+    fn assert_mismatch(ctx: &mut AnalyzerContext, stmt: &str, expected_field: &str) {
+        match analyze(ctx, stmt).expect_err("Analysis should fail") {
+            AnalyzerError::TypeMismatch { table, field, .. } => {
+                assert_eq!(table.as_deref(), Some("user"));
+                assert_eq!(field.as_deref(), Some(expected_field));
+            }
+            err => panic!("Expected a type mismatch, found {err}"),
+        }
+    }
+
+    #[test]
+    fn valid_literals() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+                DEFINE FIELD nickname ON user TYPE option<string>;
+                DEFINE FIELD tags ON user TYPE array<string>;
+                DEFINE FIELD address ON user TYPE object;
+                DEFINE FIELD address.city ON user TYPE string;
+                DEFINE FIELD best_friend ON user TYPE option<record<user>>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            r#"
+            CREATE user CONTENT {
+                name: 'Tobie',
+                age: 36,
+                nickname: NONE,
+                tags: ['admin', 'author'],
+                address: { city: 'London' },
+                best_friend: user:jaime
+            };
+            UPDATE user SET name = string::uppercase(name), address.city = 'Paris';
+            UPDATE user SET age += 1, tags += 'author', tags -= ['admin'];
+            INSERT INTO user [
+                { name: 'Jaime', age: 30, tags: [], address: { city: 'Madrid' } },
+                { name: 'Tobie', age: 36, tags: [], address: { city: 'London' } }
+            ];
+        "#,
+        )
+        .expect("Analysis should succeed");
+    }
+
+    #[test]
+    fn mismatched_literals() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+                DEFINE FIELD nickname ON user TYPE option<string>;
+                DEFINE FIELD tags ON user TYPE array<string>;
+                DEFINE FIELD address ON user TYPE object;
+                DEFINE FIELD address.city ON user TYPE string;
+                DEFINE FIELD best_friend ON user TYPE option<record<user>>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        assert_mismatch(&mut ctx, "CREATE user CONTENT { age: 'forty' };", "age");
+        assert_mismatch(&mut ctx, "CREATE user SET name = NONE;", "name");
+        assert_mismatch(&mut ctx, "UPDATE user SET tags = ['admin', 1];", "tags");
+        assert_mismatch(
+            &mut ctx,
+            "UPDATE user MERGE { address: { city: 42 } };",
+            "address.city",
+        );
+        assert_mismatch(
+            &mut ctx,
+            "CREATE user SET best_friend = post:first;",
+            "best_friend",
+        );
+        assert_mismatch(
+            &mut ctx,
+            "INSERT INTO user { name: 'Jaime', age: 30.5 };",
+            "age",
+        );
+        assert_mismatch(&mut ctx, "UPDATE user SET age += 'x';", "age");
+        assert_mismatch(&mut ctx, "UPDATE user SET tags += 1;", "tags");
+    }
+
+    fn assert_violation(ctx: &mut AnalyzerContext, stmt: &str, expected_field: &str) {
+        match analyze(ctx, stmt).expect_err("Analysis should fail") {
+            AnalyzerError::SchemaViolation { table, field, .. } => {
+                assert_eq!(table.as_deref(), Some("user"));
+                assert_eq!(field.as_deref(), Some(expected_field));
+            }
+            err => panic!("Expected a schema violation, found {err}"),
+        }
+    }
+
+    #[test]
+    fn missing_required_fields() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+                DEFINE FIELD nickname ON user TYPE option<string>;
+                DEFINE FIELD tags ON user TYPE array<string>;
+                DEFINE FIELD address ON user TYPE object;
+                DEFINE FIELD address.city ON user TYPE string;
+                DEFINE FIELD address.zip ON user TYPE option<string>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        assert_violation(&mut ctx, "CREATE user SET name = 'Tobie';", "age");
+        assert_violation(&mut ctx, "CREATE user;", "name");
+        assert_violation(
+            &mut ctx,
+            "CREATE user CONTENT { name: 'Tobie', age: 36, tags: [], address: {} };",
+            "address.city",
+        );
+        assert_violation(&mut ctx, "UPDATE user CONTENT { name: 'Tobie' };", "age");
+
+        // Nested fields set by their path count towards the object holding them.
+        let create = "CREATE user SET name = 'Tobie', age = 36, tags = []";
+        assert_violation(
+            &mut ctx,
+            &format!("{create}, address.zip = 'SW1';"),
+            "address.city",
+        );
+        assert_violation(&mut ctx, &format!("{create};"), "address");
+        analyze(
+            &mut ctx,
+            &format!(
+                "{create}, address.city = 'London'; {create}, address = {{ city: 'London' }};"
+            ),
+        )
+        .expect("Analysis should succeed");
+
+        // Partial updates keep the fields they don't mention.
+        analyze(
+            &mut ctx,
+            "UPDATE user SET age = 37; UPDATE user MERGE { address: {} };",
+        )
+        .expect("Analysis should succeed");
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD address ON user TYPE object;
+                DEFINE FIELD address.city ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        assert_violation(
+            &mut ctx,
+            "UPDATE user SET email = 'tobie@surrealdb.com';",
+            "email",
+        );
+        assert_violation(
+            &mut ctx,
+            "UPDATE user MERGE { address: { zip: 'SW1' } };",
+            "address.zip",
+        );
+    }
+
+    #[test]
+    fn permissive_tables_and_fields() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD settings ON user FLEXIBLE TYPE object;
+            DEFINE TABLE event SCHEMALESS;
+                DEFINE FIELD name ON event TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            r#"
+            CREATE event SET source = 'import';
+            UPDATE user SET settings = { theme: 'dark' }, settings.language = 'en';
+        "#,
+        )
+        .expect("Analysis should succeed");
+    }
+
+    #[test]
+    fn input_types() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE account SCHEMAFULL;
+                DEFINE FIELD name ON account TYPE string;
+                DEFINE FIELD slug ON account TYPE string VALUE string::lowercase(name);
+                DEFINE FIELD created ON account TYPE datetime DEFAULT time::now() READONLY;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            "CREATE account CONTENT $account; UPDATE account MERGE $changes;",
+        )
+        .expect("Analysis should succeed");
+
+        assert_eq!(
+            ctx.get_inferred_param("account"),
+            Some(&kind!(
+                "{ name: string, slug: option<string>, created: option<datetime> }"
+            ))
+        );
+        assert_eq!(
+            ctx.get_inferred_param("changes"),
+            Some(&kind!("{ name: option<string>, slug: option<string> }"))
+        );
+    }
+
+    #[test]
+    fn nested_input_types() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+                DEFINE FIELD nickname ON user TYPE option<string>;
+                DEFINE FIELD tags ON user TYPE array<string>;
+                DEFINE FIELD address ON user TYPE object;
+                DEFINE FIELD address.city ON user TYPE string;
+                DEFINE FIELD best_friend ON user TYPE option<record<user>>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            "CREATE user CONTENT $user; UPDATE user MERGE $changes;",
+        )
+        .expect("Analysis should succeed");
+
+        assert_eq!(
+            ctx.get_inferred_param("user"),
+            Some(&kind!(
+                "{ name: string, age: int, nickname: option<string>, tags: array<string>, address: { city: string }, best_friend: option<record<user>> }"
+            ))
+        );
+        assert_eq!(
+            ctx.get_inferred_param("changes"),
+            Some(&kind!(
+                "{ name: option<string>, age: option<int>, nickname: option<string>, tags: option<array<string>>, address: option<{ city: option<string> }>, best_friend: option<record<user>> }"
+            ))
+        );
+    }
+
+    #[test]
+    fn readonly_fields() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+                DEFINE FIELD tags ON user TYPE array<string>;
+                DEFINE FIELD address ON user TYPE object;
+                DEFINE FIELD address.city ON user TYPE string;
+                DEFINE FIELD joined ON user TYPE datetime DEFAULT time::now() READONLY;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        assert_violation(&mut ctx, "UPDATE user SET joined = time::now();", "joined");
+        assert_violation(
+            &mut ctx,
+            "UPDATE user MERGE { joined: time::now() };",
+            "joined",
+        );
+        assert_violation(&mut ctx, "UPSERT user SET joined = time::now();", "joined");
+
+        // Created records may set them once.
+        analyze(
+            &mut ctx,
+            r#"
+            CREATE user CONTENT {
+                name: 'Tobie',
+                age: 36,
+                tags: [],
+                address: { city: 'London' },
+                joined: time::now()
+            };
+        "#,
+        )
+        .expect("Analysis should succeed");
+    }
+
+    fn assert_denied(ctx: &mut AnalyzerContext, stmt: &str, expected_resource: &str) {
+        match analyze(ctx, stmt).expect_err("Analysis should fail") {
+            AnalyzerError::PermissionDenied { resource, .. } => {
+                assert_eq!(resource, expected_resource)
+            }
+            err => panic!("Expected a permission error, found {err}"),
+        }
+    }
+
+    #[test]
+    fn write_permissions() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+            DEFINE TABLE note SCHEMAFULL
+                PERMISSIONS FOR select, create FULL FOR update WHERE author = $auth;
+                DEFINE FIELD text ON note TYPE string;
+                DEFINE FIELD author ON note TYPE record<user> PERMISSIONS FOR update NONE;
+            DEFINE ACCESS account ON DATABASE TYPE RECORD
+                SIGNIN (SELECT * FROM user WHERE name = $name);
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        // Root queries aren't limited by PERMISSIONS.
+        analyze(&mut ctx, "UPDATE note SET author = user:tobie;").expect("Analysis should succeed");
+
+        ctx.set_access("account").expect("Access should exist");
+        analyze(
+            &mut ctx,
+            "CREATE note SET text = 'Hello', author = $auth; UPDATE note SET text = 'Hi';",
+        )
+        .expect("Analysis should succeed");
+        assert_denied(&mut ctx, "CREATE user SET name = 'Tobie';", "user");
+        assert_denied(
+            &mut ctx,
+            "UPDATE note SET author = user:tobie;",
+            "note.author",
+        );
+        assert_denied(
+            &mut ctx,
+            "UPDATE note MERGE { author: user:tobie };",
+            "note.author",
+        );
+
+        // Viewers can only read.
+        ctx.set_role(Role::Viewer);
+        assert_denied(&mut ctx, "UPDATE note SET text = 'Hi';", "note");
+    }
+}
@@ -0,0 +1,481 @@
+use std::collections::BTreeMap;
+
+use surrealdb::sql::{
+    statements::SelectStatement, Expression, Field, Function, Groups, Kind, Literal, Param, Value,
+};
+
+use super::select::{alias_field_name, default_field_name, fetches_to_chain, KindFetchExt};
+use crate::analyzer::{
+    context::AnalyzerContext,
+    error::{AnalyzerError, AnalyzerResult},
+    kind::{union_of, KindExt},
+};
+
+/// Functions that combine the values of every record in a group, as listed by
+/// surrealdb's `Function::is_aggregate`.
+const AGGREGATES: &[&str] = &[
+    "array::distinct",
+    "array::first",
+    "array::flatten",
+    "array::group",
+    "array::last",
+    "count",
+    "math::bottom",
+    "math::interquartile",
+    "math::max",
+    "math::mean",
+    "math::median",
+    "math::midhinge",
+    "math::min",
+    "math::mode",
+    "math::nearestrank",
+    "math::percentile",
+    "math::sample",
+    "math::spread",
+    "math::stddev",
+    "math::sum",
+    "math::top",
+    "math::trimean",
+    "math::variance",
+    "time::max",
+    "time::min",
+];
+
+/// Analyzes a SELECT with a GROUP BY or GROUP ALL clause, such as
+/// `SELECT country, count() AS total FROM user GROUP BY country`.
+///
+/// Every projection is an aggregate function, typed over the values of its
+/// argument across the group, one of the grouped fields, or an expression
+/// computed from them. GROUP ALL puts every record in a single group, and so
+/// returns a single row.
+pub(super) fn analyze_grouped(
+    ctx: &mut AnalyzerContext,
+    stmt: &SelectStatement,
+    document: &Kind,
+    groups: &Groups,
+) -> AnalyzerResult<Kind> {
+    let mut fields = BTreeMap::new();
+    for field in stmt.expr.0.iter() {
+        let Field::Single { expr, alias } = field else {
+            return Err(AnalyzerError::schema_violation(
+                "A grouped SELECT can't select every field with *",
+                None::<String>,
+                None::<String>,
+            ));
+        };
+        let name = match alias {
+            Some(alias) => alias_field_name(alias),
+            None => default_field_name(expr),
+        };
+
+        let grouped = groups
+            .0
+            .iter()
+            .any(|group| group.0.to_string() == name || *expr == Value::Idiom(group.0.clone()));
+        let kind = match aggregate(ctx, document, expr)? {
+            Some(kind) => kind,
+            None if grouped => ctx.resolve_in_document(document, expr)?,
+            None => analyze_computed(ctx, document, groups, expr, &name)?,
+        };
+        fields.insert(name, kind);
+    }
+
+    // Groups name a projected field, or a field of the records being grouped.
+    for group in groups.0.iter() {
+        if !fields.contains_key(&group.0.to_string()) {
+            ctx.resolve_in_document(document, &Value::Idiom(group.0.clone()))?;
+        }
+    }
+
+    let row = match stmt.expr.1 {
+        true => match fields.into_values().collect::<Vec<_>>().as_slice() {
+            [kind] => kind.clone(),
+            _ => return Err(AnalyzerError::UnexpectedSyntax),
+        },
+        false => Kind::Literal(Literal::Object(fields)),
+    };
+    let row = match stmt.fetch.as_ref() {
+        Some(fetches) => row.resolve_fetch(&fetches_to_chain(fetches), ctx),
+        None => row,
+    };
+
+    match (stmt.only, groups.0.is_empty()) {
+        (true, _) => Ok(row),
+        (false, true) => Ok(Kind::Array(Box::new(row), Some(1))),
+        (false, false) => Ok(Kind::Array(Box::new(row), None)),
+    }
+}
+
+/// Types a projection computed from aggregates and grouped fields, such as
+/// `math::sum(age) / count()`. Each aggregate call is typed over the group and
+/// replaced with a parameter of its kind, and any field left must be grouped.
+fn analyze_computed(
+    ctx: &mut AnalyzerContext,
+    document: &Kind,
+    groups: &Groups,
+    expr: &Value,
+    name: &str,
+) -> AnalyzerResult<Kind> {
+    let mut expr = expr.clone();
+    let mut aggregates = Vec::new();
+    lift_aggregates(ctx, document, &mut expr, &mut aggregates)?;
+    if !is_grouped(&expr, groups) {
+        return Err(AnalyzerError::schema_violation(
+            format!(
+                "'{}' is neither aggregated nor in the GROUP BY clause",
+                name
+            ),
+            None::<String>,
+            Some(name.to_string()),
+        ));
+    }
+
+    ctx.push_scope();
+    for (param, kind) in aggregates {
+        ctx.bind_param(&param, kind);
+    }
+    let kind = ctx.resolve_in_document(document, &expr);
+    ctx.pop_scope();
+    kind
+}
+
+/// Replaces the aggregate calls in `value` with parameters, collecting the
+/// kind each one returns.
+fn lift_aggregates(
+    ctx: &mut AnalyzerContext,
+    document: &Kind,
+    value: &mut Value,
+    aggregates: &mut Vec<(String, Kind)>,
+) -> AnalyzerResult<()> {
+    if let Some(kind) = aggregate(ctx, document, value)? {
+        // The name can't be written in a query, so it won't shadow a parameter.
+        let param = format!("aggregate {}", aggregates.len());
+        *value = Value::Param(Param::from(param.as_str()));
+        aggregates.push((param, kind));
+        return Ok(());
+    }
+
+    match value {
+        Value::Expression(expression) => match &mut **expression {
+            Expression::Unary { v, .. } => lift_aggregates(ctx, document, v, aggregates)?,
+            Expression::Binary { l, r, .. } => {
+                lift_aggregates(ctx, document, l, aggregates)?;
+                lift_aggregates(ctx, document, r, aggregates)?;
+            }
+            _ => {}
+        },
+        Value::Cast(cast) => lift_aggregates(ctx, document, &mut cast.1, aggregates)?,
+        Value::Function(function) => {
+            if let Function::Normal(_, args) | Function::Custom(_, args) = &mut **function {
+                for arg in args {
+                    lift_aggregates(ctx, document, arg, aggregates)?;
+                }
+            }
+        }
+        Value::Array(array) => {
+            for item in array.iter_mut() {
+                lift_aggregates(ctx, document, item, aggregates)?;
+            }
+        }
+        Value::Object(object) => {
+            for item in object.values_mut() {
+                lift_aggregates(ctx, document, item, aggregates)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Checks that every field `value` reads is one of the grouped fields.
+fn is_grouped(value: &Value, groups: &Groups) -> bool {
+    match value {
+        Value::Idiom(idiom) => groups.0.iter().any(|group| group.0 == *idiom),
+        Value::Expression(expression) => match &**expression {
+            Expression::Unary { v, .. } => is_grouped(v, groups),
+            Expression::Binary { l, r, .. } => is_grouped(l, groups) && is_grouped(r, groups),
+            _ => true,
+        },
+        Value::Cast(cast) => is_grouped(&cast.1, groups),
+        Value::Function(function) => function.args().iter().all(|arg| is_grouped(arg, groups)),
+        Value::Array(array) => array.iter().all(|item| is_grouped(item, groups)),
+        Value::Object(object) => object.values().all(|item| is_grouped(item, groups)),
+        _ => true,
+    }
+}
+
+/// Types an aggregate function call over a group, or returns None if `expr`
+/// isn't one.
+fn aggregate(
+    ctx: &mut AnalyzerContext,
+    document: &Kind,
+    expr: &Value,
+) -> AnalyzerResult<Option<Kind>> {
+    let Value::Function(function) = expr else {
+        return Ok(None);
+    };
+    let Function::Normal(name, args) = &**function else {
+        return Ok(None);
+    };
+    if !AGGREGATES.contains(&name.as_str()) {
+        return Ok(None);
+    }
+
+    // The first argument is evaluated on every record of the group, and the
+    // function is called with the array of its values.
+    let kinds = args
+        .iter()
+        .map(|arg| ctx.resolve_in_document(document, arg))
+        .collect::<AnalyzerResult<Vec<_>>>()?;
+    if name == "count" {
+        return match kinds.len() {
+            0 | 1 => Ok(Some(Kind::Int)),
+            _ => Err(invalid_call(name, "count takes at most one argument")),
+        };
+    }
+
+    // A few aggregates take a number besides the values of the group.
+    let takes_number = matches!(
+        name.as_str(),
+        "math::bottom" | "math::top" | "math::percentile" | "math::nearestrank"
+    );
+    let kind = match (kinds.as_slice(), takes_number) {
+        ([kind], false) => kind,
+        ([kind, number], true) => {
+            check_number(number)?;
+            kind
+        }
+        (_, false) => {
+            return Err(invalid_call(
+                name,
+                "An aggregate function takes a single argument",
+            ))
+        }
+        (_, true) => {
+            return Err(invalid_call(
+                name,
+                "This aggregate function takes a field and a number",
+            ))
+        }
+    };
+
+    let kind = match name.as_str() {
+        "math::max" | "math::min" | "math::mode" => check_number(kind)?,
+        "math::bottom" | "math::top" => Kind::Array(Box::new(check_number(kind)?), None),
+        name if name.starts_with("math::") && name != "math::sample" => {
+            check_number(kind)?;
+            Kind::Number
+        }
+        "time::max" | "time::min" => {
+            let value = kind.without_none();
+            if value != Kind::Any && !Kind::Datetime.is_assignable_from(&value) {
+                return Err(AnalyzerError::type_mismatch(&Kind::Datetime, kind));
+            }
+            Kind::Datetime
+        }
+        // The first or last value of the group.
+        "array::first" | "array::last" => kind.clone(),
+        // array::flatten and array::group flatten the arrays they collect.
+        "array::flatten" | "array::group" => match kind {
+            Kind::Array(inner, _) | Kind::Set(inner, _) => Kind::Array(inner.clone(), None),
+            Kind::Literal(Literal::Array(items)) => {
+                Kind::Array(Box::new(union_of(items.iter().cloned())), None)
+            }
+            _ => Kind::Array(Box::new(kind.clone()), None),
+        },
+        _ => Kind::Array(Box::new(kind.clone()), None),
+    };
+    Ok(Some(kind))
+}
+
+/// Checks that `kind` holds numbers, returning the kind of those numbers.
+fn check_number(kind: &Kind) -> AnalyzerResult<Kind> {
+    let value = kind.without_none();
+    if value != Kind::Any && !value.is_numeric() {
+        return Err(AnalyzerError::type_mismatch(&Kind::Number, kind));
+    }
+    Ok(value)
+}
+
+fn invalid_call(function: &str, message: &str) -> AnalyzerError {
+    AnalyzerError::InvalidFunctionCall {
+        function: function.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn group_by() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD country ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+                DEFINE FIELD tags ON user TYPE array<string>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let stmt = r#"
+            SELECT country, count() AS n, math::mean(age) AS avg, math::max(age) AS oldest,
+                array::group(tags) AS tags, array::distinct(name) AS names
+            FROM user GROUP BY country;
+        "#;
+        let analyzed_kind = analyze(&mut ctx, stmt).expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!(
+                "[array<{ country: string, n: int, avg: number, oldest: int, tags: array<string>, names: array<string> }>]"
+            )
+        );
+    }
+
+    #[test]
+    fn statistics() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD country ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+                DEFINE FIELD tags ON user TYPE array<string>;
+                DEFINE FIELD created ON user TYPE datetime;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let stmt = r#"
+            SELECT country, math::median(age) AS median, math::stddev(age) AS stddev,
+                math::percentile(age, 90) AS p90, math::top(age, 3) AS oldest,
+                time::max(created) AS latest, array::first(name) AS first,
+                array::flatten(tags) AS tags
+            FROM user GROUP BY country;
+        "#;
+        let analyzed_kind = analyze(&mut ctx, stmt).expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!(
+                "[array<{ country: string, median: number, stddev: number, p90: number, oldest: array<int>, latest: datetime, first: string, tags: array<string> }>]"
+            )
+        );
+
+        for stmt in [
+            "SELECT math::percentile(age) AS p FROM user GROUP ALL;",
+            "SELECT math::median(age, 2) AS m FROM user GROUP ALL;",
+        ] {
+            let err = analyze(&mut ctx, stmt).expect_err("Analysis should fail");
+            assert!(
+                matches!(err, AnalyzerError::InvalidFunctionCall { .. }),
+                "{stmt} should be rejected"
+            );
+        }
+
+        let err = analyze(&mut ctx, "SELECT time::max(name) AS t FROM user GROUP ALL;")
+            .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn computed_aggregates() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD country ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let stmt = r#"
+            SELECT country, math::sum(age) / count() AS avg, <string> count() AS total,
+                string::concat(country, ': ', <string> math::max(age)) AS label
+            FROM user GROUP BY country;
+        "#;
+        let analyzed_kind = analyze(&mut ctx, stmt).expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[array<{ country: string, avg: number, total: string, label: string }>]")
+        );
+
+        let err = analyze(
+            &mut ctx,
+            "SELECT country, math::sum(age) + age AS total FROM user GROUP BY country;",
+        )
+        .expect_err("Analysis should fail");
+        assert!(err.is_schema_violation());
+    }
+
+    #[test]
+    fn group_all() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD age ON user TYPE int;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            SELECT count() AS total, math::sum(age) AS ages FROM user GROUP ALL;
+            SELECT VALUE count() FROM user GROUP ALL;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[array<{ total: int, ages: number }, 1>, array<int, 1>]")
+        );
+    }
+
+    #[test]
+    fn invalid_groups() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD country ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        for stmt in [
+            "SELECT name, country, count() AS n FROM user GROUP BY country;",
+            "SELECT name FROM user GROUP ALL;",
+            "SELECT * FROM user GROUP BY country;",
+        ] {
+            let err = analyze(&mut ctx, stmt).expect_err("Analysis should fail");
+            assert!(err.is_schema_violation(), "{stmt} should be rejected");
+        }
+
+        let err = analyze(&mut ctx, "SELECT math::sum(name) AS n FROM user GROUP ALL;")
+            .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
+
+        let err = analyze(
+            &mut ctx,
+            "SELECT email, count() AS n FROM user GROUP BY email;",
+        )
+        .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::FieldNotFound { .. }));
+    }
+}
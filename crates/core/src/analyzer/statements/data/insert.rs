@@ -1,3 +1,4 @@
+use super::fields::{analyze_data, Write};
 use crate::analyzer::{
     context::AnalyzerContext,
     error::{AnalyzerError, AnalyzerResult},
@@ -12,16 +13,24 @@ pub fn analyze_insert(ctx: &mut AnalyzerContext, stmt: &InsertStatement) -> Anal
     let what = stmt.into.as_ref().unwrap();
 
     let kind = ctx.resolve(what)?;
-    let target_type = match kind {
+    let table_name = match kind {
         Kind::Record(tables) => {
             if let Some(table) = tables.first() {
-                ctx.build_full_table_type(&table.0)?
+                table.0.clone()
             } else {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
         }
         _ => return Err(AnalyzerError::UnexpectedSyntax),
     };
+
+    // Check the inserted records, and the ON DUPLICATE KEY UPDATE clause.
+    analyze_data(ctx, &table_name, Some(&stmt.data), Write::Create)?;
+    if let Some(update) = &stmt.update {
+        analyze_data(ctx, &table_name, Some(update), Write::Update)?;
+    }
+
+    let target_type = ctx.build_full_table_type(&table_name)?;
     Ok(Kind::Array(Box::new(target_type), None))
 }
 
@@ -47,7 +56,7 @@ mod tests {
         let stmt = "INSERT INTO user { name: 'Jane', age: 30 };";
         let analyzed_kind = analyze(&mut ctx, stmt).expect("Analysis should succeed");
 
-        let expected_kind = kind!("array<array<{ name: string, age: number }>>");
+        let expected_kind = kind!("[array<{ name: string, age: number }>]");
         assert_eq!(analyzed_kind, expected_kind);
     }
 }
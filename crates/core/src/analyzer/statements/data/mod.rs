@@ -1,5 +1,8 @@
+mod clauses; // ORDER, LIMIT, START, SPLIT and WITH clauses of SELECT statements
 mod create; // CREATE statements
 mod delete; // DELETE statements
+mod fields; // Data clauses of write statements
+mod group; // GROUP clauses of SELECT statements
 mod insert; // INSERT statements (separate from CREATE)
 mod relate; // RELATE statements (for graph relationships)
 mod select; // SELECT statements
@@ -10,6 +13,6 @@ pub use create::analyze_create;
 pub use delete::analyze_delete;
 pub use insert::analyze_insert;
 pub use relate::analyze_relate;
-pub use select::analyze_select;
+pub use select::{analyze_select, fetches_to_chain, KindFetchExt};
 pub use update::analyze_update;
 pub use upsert::analyze_upsert;
@@ -1,8 +1,9 @@
+use super::fields::{analyze_data, Write};
 use crate::analyzer::{
     context::AnalyzerContext,
     error::{AnalyzerError, AnalyzerResult},
 };
-use surrealdb::sql::{statements::RelateStatement, Data, Idiom, Kind, Value};
+use surrealdb::sql::{statements::RelateStatement, Kind, Value};
 
 /// Analyzes a RELATE statement.
 ///
@@ -38,34 +39,8 @@ pub fn analyze_relate(ctx: &mut AnalyzerContext, stmt: &RelateStatement) -> Anal
         }
     }
 
-    // Handle content parameters if present
-    if let Some(data) = &stmt.data {
-        match data {
-            Data::ContentExpression(value) => {
-                match value {
-                    Value::Param(param_name) => {
-                        // For RELATE ... CONTENT $param
-                        ctx.infer_param_from_table(&relation_table, param_name)?;
-                    }
-                    Value::Object(obj) => {
-                        // For RELATE ... CONTENT { field: $param }
-                        for (field, value) in obj.iter() {
-                            if let Value::Param(param_name) = value {
-                                let field_idiom = Idiom::from(field.clone());
-                                ctx.infer_param_from_field(
-                                    &relation_table,
-                                    &field_idiom,
-                                    param_name,
-                                )?;
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-            _ => {}
-        }
-    }
+    // Infer parameters from, and check values against, the fields they're written to.
+    analyze_data(ctx, &relation_table, stmt.data.as_ref(), Write::Create)?;
 
     // Build and return the relation type
     let relation_full_type = ctx.build_full_table_type(&relation_table)?;
@@ -95,7 +70,7 @@ mod tests {
         )
         .expect("Schema construction should succeed");
 
-        let stmt = "RELATE $person->memberOf->org:acme;";
+        let stmt = "RELATE $person->memberOf->org:acme SET role = 'member';";
         analyze(&mut ctx, stmt).expect("Analysis should succeed");
 
         let params = ctx.get_all_inferred_params();
@@ -120,7 +95,7 @@ mod tests {
         )
         .expect("Schema construction should succeed");
 
-        let stmt = "RELATE user:john->memberOf->$organization;";
+        let stmt = "RELATE user:john->memberOf->$organization SET role = 'member';";
         analyze(&mut ctx, stmt).expect("Analysis should succeed");
 
         let params = ctx.get_all_inferred_params();
@@ -145,7 +120,7 @@ mod tests {
         )
         .expect("Schema construction should succeed");
 
-        let stmt = "RELATE $person->memberOf->$organization;";
+        let stmt = "RELATE $person->memberOf->$organization SET role = 'member';";
         analyze(&mut ctx, stmt).expect("Analysis should succeed");
 
         let params = ctx.get_all_inferred_params();
@@ -198,10 +173,11 @@ mod tests {
         )
         .expect("Schema construction should succeed");
 
-        let stmt = "RELATE user:alice->memberOf->org:google";
+        let stmt =
+            "RELATE user:alice->memberOf->org:google SET role = 'owner', since = time::now()";
         let analyzed_kind = analyze(&mut ctx, stmt).expect("Analysis should succeed");
 
-        let expected_kind = kind!("array<array<{ role: string, since: datetime }>>");
+        let expected_kind = kind!("[array<{ role: string, since: datetime }>]");
         assert_eq!(analyzed_kind, expected_kind);
     }
 }
@@ -4,6 +4,7 @@
 ///
 /// - Field resolution and type checking
 /// - Support for aliases and wildcards
+/// - Computed fields (functions, expressions, casts, objects)
 /// - FETCH clause analysis
 /// - Graph traversal validation
 /// - Destructuring support
@@ -31,32 +32,49 @@
 /// - SELECT VALUE: array<type>
 /// - With FETCH: Expanded record types
 /// - Graph queries: Nested object structure
+use super::clauses::analyze_clauses;
+use super::group::analyze_grouped;
 use crate::analyzer::{
-    context::AnalyzerContext,
+    context::{Action, AnalyzerContext, Grant},
     error::{AnalyzerError, AnalyzerResult},
+    kind::union_of,
 };
 use std::collections::BTreeMap;
 use surrealdb::sql::{
-    statements::{DefineStatement, SelectStatement},
-    Dir, Fetch, Field, Idiom, Idioms, Kind, Literal, Part, Table, Value,
+    statements::SelectStatement, Dir, Fetch, Field, Function, Idiom, Idioms, Kind, Literal, Part,
+    Table, Value,
 };
 
-pub fn analyze_select(context: &AnalyzerContext, stmt: &SelectStatement) -> AnalyzerResult<Kind> {
+pub fn analyze_select(
+    context: &mut AnalyzerContext,
+    stmt: &SelectStatement,
+) -> AnalyzerResult<Kind> {
+    // `@N@` operators are referred to by the statement using them only, so a
+    // subquery's references don't leak into the enclosing statement.
+    let outer = context.take_match_refs();
+    let kind = analyze_select_statement(context, stmt)
+        .and_then(|kind| analyze_clauses(context, stmt, kind));
+    context.restore_match_refs(outer);
+    kind
+}
+
+fn analyze_select_statement(
+    context: &mut AnalyzerContext,
+    stmt: &SelectStatement,
+) -> AnalyzerResult<Kind> {
     let table_value = stmt.what.0.first().ok_or(AnalyzerError::UnexpectedSyntax)?;
     let raw_table_name = match table_value {
         Value::Table(t) => t.0.clone(),
         Value::Thing(thing) => thing.tb.clone(),
-        Value::Param(p) => match p.trim() {
-            "$auth" => match context.auth() {
+        Value::Param(p) => match p.as_str() {
+            "auth" => match context.auth() {
                 Some(auth) => auth.to_string(),
                 None => return Err(AnalyzerError::MissingAuth),
             },
-            "$token" => {
-                todo!("Implement token inference")
-            }
-            _ => return Err(AnalyzerError::UnexpectedSyntax),
+            _ => return analyze_select_from_value(context, stmt, table_value),
         },
-        _ => return Err(AnalyzerError::UnexpectedSyntax),
+        // Subqueries and other values are iterated over like a table.
+        _ => return analyze_select_from_value(context, stmt, table_value),
     };
     let table_name = if raw_table_name.contains(':') {
         raw_table_name.split(':').next().unwrap().to_string()
@@ -68,6 +86,14 @@ pub fn analyze_select(context: &AnalyzerContext, stmt: &SelectStatement) -> Anal
         return Err(AnalyzerError::TableNotFound(table_name));
     }
 
+    let document = Kind::Record(vec![Table::from(table_name.clone())]);
+    if let Some(cond) = &stmt.cond {
+        context.resolve_in_document(&document, &cond.0)?;
+    }
+    if let Some(groups) = &stmt.group {
+        return analyze_grouped(context, stmt, &document, groups);
+    }
+
     let is_value_select = stmt.expr.1;
 
     if is_value_select {
@@ -76,45 +102,55 @@ pub fn analyze_select(context: &AnalyzerContext, stmt: &SelectStatement) -> Anal
         }
         match &stmt.expr.0[0] {
             Field::Single { expr, .. } => {
-                let field_idiom = match expr {
-                    Value::Idiom(idiom) => idiom,
-                    _ => return Err(AnalyzerError::UnexpectedSyntax),
-                };
-
-                if let Some(DefineStatement::Field(field_def)) =
-                    context.find_field_definition(&table_name, field_idiom)
-                {
-                    let mut resolved = field_def.kind.clone().unwrap_or(Kind::Any);
-                    if let Some(fetches) = stmt.fetch.as_ref() {
-                        let fetch_chain = fetches_to_chain(fetches);
-                        resolved = resolved.resolve_fetch(&fetch_chain, context);
-                    }
-                    return Ok(Kind::Literal(Literal::Array(vec![resolved])));
-                } else {
-                    return Err(AnalyzerError::field_not_found(
-                        field_idiom.to_string(),
-                        &table_name,
-                    ));
+                let mut resolved = context.resolve_in_document(&document, expr)?;
+                if let Some(fetches) = stmt.fetch.as_ref() {
+                    let fetch_chain = fetches_to_chain(fetches);
+                    resolved = resolved.resolve_fetch(&fetch_chain, context);
                 }
+                return Ok(match stmt.only {
+                    true => resolved,
+                    false => Kind::Array(Box::new(resolved), None),
+                });
             }
             _ => return Err(AnalyzerError::UnexpectedSyntax),
         }
     }
 
-    let base_kind = if stmt.expr.0.is_empty() || stmt.expr.0.iter().any(|f| matches!(f, Field::All))
-    {
-        build_full_table_type(context, &table_name, stmt.omit.as_ref())?
-    } else {
-        let mut field_types = BTreeMap::new();
+    let base_kind = {
+        // `*` selects every defined field, and can be combined with other projections.
+        let mut field_types =
+            if stmt.expr.0.is_empty() || stmt.expr.0.iter().any(|f| matches!(f, Field::All)) {
+                match build_full_table_type(context, &table_name, stmt.omit.as_ref())? {
+                    Kind::Literal(Literal::Object(fields)) => fields,
+                    _ => BTreeMap::new(),
+                }
+            } else {
+                BTreeMap::new()
+            };
         for field in &stmt.expr.0 {
             match field {
+                Field::All => continue,
                 Field::Single { expr, alias } => {
                     let field_idiom = match expr {
                         Value::Idiom(idiom) => idiom,
-                        _ => return Err(AnalyzerError::UnexpectedSyntax),
+                        // Computed fields are named by their alias, or by the
+                        // expression itself when no alias is given.
+                        _ => {
+                            let output_name = match alias {
+                                Some(alias_name) => alias_field_name(alias_name),
+                                None => default_field_name(expr),
+                            };
+                            let kind = context.resolve_in_document(&document, expr)?;
+                            field_types.insert(output_name, kind);
+                            continue;
+                        }
                     };
 
-                    if should_omit_field(field_idiom, stmt.omit.as_ref()) {
+                    // Fields the access level can't select are never returned.
+                    if should_omit_field(field_idiom, stmt.omit.as_ref())
+                        || context.field_grant(&table_name, field_idiom, Action::Select)
+                            == Grant::Denied
+                    {
                         continue;
                     }
 
@@ -126,7 +162,7 @@ pub fn analyze_select(context: &AnalyzerContext, stmt: &SelectStatement) -> Anal
                             // For aliased paths, extract the innermost array type
                             if let Kind::Literal(Literal::Object(graph_fields)) = graph_type {
                                 let final_type = extract_final_type(&graph_fields);
-                                field_types.insert(alias_name.to_string(), final_type);
+                                field_types.insert(alias_field_name(alias_name), final_type);
                             }
                         } else {
                             // No alias - use the full path structure
@@ -139,7 +175,7 @@ pub fn analyze_select(context: &AnalyzerContext, stmt: &SelectStatement) -> Anal
 
                     // Handle destructuring
                     if let Some((parent_path, fields)) = get_destructure_parts(field_idiom) {
-                        if let Some(DefineStatement::Field(parent_field_def)) =
+                        if let Some(parent_field_def) =
                             context.find_field_definition(&table_name, &parent_path)
                         {
                             if let Some(Kind::Literal(Literal::Object(parent_type))) =
@@ -152,7 +188,7 @@ pub fn analyze_select(context: &AnalyzerContext, stmt: &SelectStatement) -> Anal
                                     }
                                 }
                                 let output_name = if let Some(alias_name) = alias {
-                                    alias_name.to_string()
+                                    alias_field_name(alias_name)
                                 } else {
                                     parent_path.to_string()
                                 };
@@ -166,29 +202,13 @@ pub fn analyze_select(context: &AnalyzerContext, stmt: &SelectStatement) -> Anal
                     }
 
                     // Regular field handling
-                    if let Some(DefineStatement::Field(field_def)) =
-                        context.find_field_definition(&table_name, field_idiom)
-                    {
-                        let output_name = if let Some(alias_name) = alias {
-                            alias_name.to_string()
-                        } else {
-                            field_idiom.to_string()
-                        };
-                        if let Some(kind) = field_def.kind.clone() {
-                            field_types.insert(output_name, kind);
-                        } else {
-                            return Err(AnalyzerError::schema_violation(
-                                "Field type not defined",
-                                Some(&table_name),
-                                Some(&field_idiom.to_string()),
-                            ));
-                        }
+                    let output_name = if let Some(alias_name) = alias {
+                        alias_field_name(alias_name)
                     } else {
-                        return Err(AnalyzerError::field_not_found(
-                            field_idiom.to_string(),
-                            &table_name,
-                        ));
-                    }
+                        field_idiom.to_string()
+                    };
+                    let kind = context.resolve_in_document(&document, expr)?;
+                    field_types.insert(output_name, kind);
                 }
                 _ => return Err(AnalyzerError::UnexpectedSyntax),
             }
@@ -212,6 +232,112 @@ pub fn analyze_select(context: &AnalyzerContext, stmt: &SelectStatement) -> Anal
     }
 }
 
+/// Analyzes a SELECT over the result of a value rather than a table, such as
+/// `SELECT name FROM (SELECT * FROM user)`.
+///
+/// Projections and conditions are resolved against the element kind of the
+/// value, as no field definitions apply to it directly.
+fn analyze_select_from_value(
+    context: &mut AnalyzerContext,
+    stmt: &SelectStatement,
+    source: &Value,
+) -> AnalyzerResult<Kind> {
+    let document = match context.resolve(source)? {
+        Kind::Array(inner, _) | Kind::Set(inner, _) => *inner,
+        Kind::Literal(Literal::Array(items)) => union_of(items),
+        kind => kind,
+    };
+
+    if let Some(cond) = &stmt.cond {
+        context.resolve_in_document(&document, &cond.0)?;
+    }
+    if let Some(groups) = &stmt.group {
+        return analyze_grouped(context, stmt, &document, groups);
+    }
+
+    let projected = if stmt.expr.1 {
+        match stmt.expr.0.as_slice() {
+            [Field::Single { expr, .. }] => context.resolve_in_document(&document, expr)?,
+            _ => return Err(AnalyzerError::UnexpectedSyntax),
+        }
+    } else {
+        let wildcard =
+            stmt.expr.0.is_empty() || stmt.expr.0.iter().any(|f| matches!(f, Field::All));
+        let mut field_types = match &document {
+            Kind::Literal(Literal::Object(fields)) if wildcard => fields.clone(),
+            Kind::Record(tables) if wildcard && tables.len() == 1 => {
+                match context.build_full_table_type(&tables[0].0)? {
+                    Kind::Literal(Literal::Object(fields)) => fields,
+                    _ => BTreeMap::new(),
+                }
+            }
+            // Every field of a value of unknown shape is selected.
+            _ if wildcard && stmt.expr.0.len() == 1 => {
+                return Ok(Kind::Array(Box::new(Kind::Any), None));
+            }
+            _ => BTreeMap::new(),
+        };
+        if let Some(idioms) = stmt.omit.as_ref() {
+            for idiom in idioms.0.iter() {
+                remove_nested_field(&mut field_types, &idiom.0);
+            }
+        }
+
+        for field in &stmt.expr.0 {
+            if let Field::Single { expr, alias } = field {
+                let output_name = match alias {
+                    Some(alias_name) => alias_field_name(alias_name),
+                    None => default_field_name(expr),
+                };
+                let kind = context.resolve_in_document(&document, expr)?;
+                field_types.insert(output_name, kind);
+            }
+        }
+        Kind::Literal(Literal::Object(field_types))
+    };
+
+    let transformed_kind = if let Some(fetches) = stmt.fetch.as_ref() {
+        let fetch_chain = fetches_to_chain(fetches);
+        projected.resolve_fetch(&fetch_chain, context)
+    } else {
+        projected
+    };
+
+    if stmt.only {
+        Ok(transformed_kind)
+    } else {
+        Ok(Kind::Array(Box::new(transformed_kind), None))
+    }
+}
+
+/// Returns the name of the field an alias projects to. Keywords such as `info`
+/// are escaped when the alias is printed, but not in the field's name.
+pub(super) fn alias_field_name(alias: &Idiom) -> String {
+    match alias.0.as_slice() {
+        [Part::Field(ident)] => ident.to_raw(),
+        _ => alias.to_string(),
+    }
+}
+
+/// Returns the name SurrealDB gives an un-aliased projection.
+///
+/// Function calls are named after the function (`count`, `string::uppercase`,
+/// `fn::greet`), parameters after the parameter, and any other expression after
+/// its own SurrealQL text (`age + 1`).
+pub(super) fn default_field_name(expr: &Value) -> String {
+    match expr {
+        Value::Idiom(idiom) => idiom.to_string(),
+        Value::Param(param) => param.as_str().to_string(),
+        Value::Strand(strand) => strand.as_str().to_string(),
+        Value::Function(function) => match &**function {
+            Function::Normal(name, _) => name.clone(),
+            Function::Custom(name, _) => format!("fn::{}", name),
+            _ => "function".to_string(),
+        },
+        _ => expr.to_string(),
+    }
+}
+
 fn extract_final_type(fields: &BTreeMap<String, Kind>) -> Kind {
     // We expect only one key in each level
     if let Some((_key, value)) = fields.iter().next() {
@@ -350,7 +476,7 @@ pub fn analyze_graph_path(context: &AnalyzerContext, field_idiom: &Idiom) -> Ana
 
 /// Restricts a full table type (assumed to be a Literal::Object) to only include the given list of fields.
 /// If the type is not a literal object, it is returned unchanged.
-fn restrict_type(kind: Kind, fields: &Vec<String>) -> Kind {
+fn restrict_type(kind: Kind, fields: &[String]) -> Kind {
     match kind {
         Kind::Literal(Literal::Object(map)) => {
             let new_map = map
@@ -398,12 +524,10 @@ fn build_full_table_type(
     table_name: &str,
     omit_idioms: Option<&Idioms>,
 ) -> AnalyzerResult<Kind> {
-    let mut field_types = BTreeMap::new();
-    for field_def in context.get_field_definitions(table_name) {
-        if let Some(kind) = field_def.kind.clone() {
-            field_types.insert(field_def.name.to_string(), kind);
-        }
-    }
+    let mut field_types = match context.build_full_table_type(table_name)? {
+        Kind::Literal(Literal::Object(fields)) => fields,
+        _ => BTreeMap::new(),
+    };
     if let Some(idioms) = omit_idioms {
         for idiom in idioms.0.iter() {
             remove_nested_field(&mut field_types, &idiom.0);
@@ -446,7 +570,7 @@ fn normalized_part(part: &Part) -> String {
 }
 
 /// Converts a vector of Fetch items into a chain of strings representing the fetch path.
-fn fetches_to_chain(fetches: &Vec<Fetch>) -> Vec<String> {
+pub fn fetches_to_chain(fetches: &[Fetch]) -> Vec<String> {
     fetches
         .iter()
         .map(|f| f.0.to_string().trim().to_lowercase())
@@ -525,7 +649,10 @@ impl KindFetchExt for Kind {
 #[cfg(test)]
 mod tests {
     use crate::{
-        analyzer::{analyze, context::AnalyzerContext},
+        analyzer::{
+            analyze,
+            context::{AnalyzerContext, Role},
+        },
         prelude::AnalyzerResult,
     };
     use surrealdb::sql::{Kind, Statement};
@@ -625,6 +752,27 @@ mod tests {
         assert_eq!(analyzed_kind, expected_kind);
     }
 
+    #[test]
+    fn keyword_aliases() {
+        let stmt = "SELECT name AS info, address.{ city } AS type FROM user;";
+
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD address ON user TYPE { city: string, zip: string };
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze_select(&mut ctx, stmt).expect("Analysis should succeed");
+        let expected_kind = kind!("array<{ info: string, type: { city: string } }>");
+
+        assert_eq!(analyzed_kind, expected_kind);
+    }
+
     #[test]
     fn omit() {
         let stmt = "SELECT * OMIT age, address.zip FROM user;";
@@ -709,9 +857,12 @@ mod tests {
         let mut ctx = AnalyzerContext::new();
         analyze(&mut ctx, schema).expect("Schema construction should succeed");
         let analyzed_kind = analyze_select(&mut ctx, query).expect("Analysis should succeed");
-        // Changed to match the actual structure: Array(Literal(Array([String])), None)
-        let expected_kind = kind!("[string]");
+        let expected_kind = kind!("array<string>");
         assert_eq!(analyzed_kind, expected_kind);
+
+        let query = "SELECT VALUE email FROM ONLY user:tobie;";
+        let analyzed_kind = analyze_select(&mut ctx, query).expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("string"));
     }
 
     #[test]
@@ -1007,4 +1158,180 @@ mod tests {
 
         assert_eq!(analyzed_kind, expected_kind);
     }
+
+    #[test]
+    fn computed_fields() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let stmt = "SELECT name, string::uppercase(name) AS upper, count() AS n FROM user;";
+        let analyzed_kind = analyze_select(&mut ctx, stmt).expect("Analysis should succeed");
+        let expected_kind = kind!("array<{ name: string, upper: string, n: int }>");
+        assert_eq!(analyzed_kind, expected_kind);
+
+        let stmt = "SELECT age + 1, string::lowercase(name), <string> age AS label, { first: name } AS info FROM user;";
+        let analyzed_kind = analyze_select(&mut ctx, stmt).expect("Analysis should succeed");
+        let expected_kind = kind!(
+            r#"array<{
+            "age + 1": int,
+            "string::lowercase": string,
+            label: string,
+            info: { first: string }
+        }>"#
+        );
+        assert_eq!(analyzed_kind, expected_kind);
+    }
+
+    #[test]
+    fn computed_fields_with_wildcard() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let stmt = "SELECT *, string::len(name) AS length FROM user;";
+        let analyzed_kind = analyze_select(&mut ctx, stmt).expect("Analysis should succeed");
+        let expected_kind = kind!("array<{ name: string, length: int }>");
+        assert_eq!(analyzed_kind, expected_kind);
+    }
+
+    #[test]
+    fn subquery_field() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD title ON post TYPE string;
+                DEFINE FIELD author ON post TYPE record<user>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let stmt =
+            "SELECT *, (SELECT title FROM post WHERE author = $parent.id) AS posts FROM user;";
+        let analyzed_kind = analyze_select(&mut ctx, stmt).expect("Analysis should succeed");
+        let expected_kind = kind!("array<{ name: string, posts: array<{ title: string }> }>");
+        assert_eq!(analyzed_kind, expected_kind);
+    }
+
+    #[test]
+    fn subquery_target() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD title ON post TYPE string;
+                DEFINE FIELD author ON post TYPE record<user>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let stmt = "SELECT title, author.name AS author FROM (SELECT * FROM post);";
+        let analyzed_kind = analyze_select(&mut ctx, stmt).expect("Analysis should succeed");
+        let expected_kind = kind!("array<{ title: string, author: string }>");
+        assert_eq!(analyzed_kind, expected_kind);
+    }
+
+    #[test]
+    fn subquery_checks_parent_fields() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD title ON post TYPE string;
+                DEFINE FIELD author ON post TYPE record<user>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let stmt =
+            "SELECT (SELECT * FROM post WHERE author.name = $parent.email) AS posts FROM user;";
+        let err = analyze_select(&mut ctx, stmt).expect_err("Analysis should fail");
+        assert!(matches!(
+            err,
+            crate::analyzer::error::AnalyzerError::FieldNotFound { .. }
+        ));
+    }
+
+    #[test]
+    fn field_permissions() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL PERMISSIONS FOR select FULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD email ON user TYPE string PERMISSIONS FOR select WHERE id = $auth.id;
+                DEFINE FIELD password ON user TYPE string PERMISSIONS FOR select NONE;
+            DEFINE ACCESS account ON DATABASE TYPE RECORD
+                SIGNIN (SELECT * FROM user WHERE email = $email);
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let stmt = "SELECT * FROM user;";
+        let root_kind = kind!("array<{ name: string, email: string, password: string }>");
+        let analyzed_kind = analyze_select(&mut ctx, stmt).expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, root_kind);
+
+        ctx.set_access("account").expect("Access should exist");
+        let analyzed_kind = analyze_select(&mut ctx, stmt).expect("Analysis should succeed");
+        let expected_kind = kind!("array<{ name: string, email: option<string> }>");
+        assert_eq!(analyzed_kind, expected_kind);
+
+        let analyzed_kind = analyze_select(&mut ctx, "SELECT name, password FROM user;")
+            .expect("Analysis should succeed");
+        let expected_kind = kind!("array<{ name: string }>");
+        assert_eq!(analyzed_kind, expected_kind);
+
+        // System users aren't limited by PERMISSIONS.
+        ctx.set_role(Role::Viewer);
+        let analyzed_kind = analyze_select(&mut ctx, stmt).expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, root_kind);
+    }
+
+    #[test]
+    fn permissions_on_escaped_names() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE `user-account` SCHEMAFULL PERMISSIONS FOR select FULL;
+                DEFINE FIELD name ON `user-account` TYPE string;
+                DEFINE FIELD `pass-hash` ON `user-account` TYPE string PERMISSIONS FOR select NONE;
+                DEFINE FIELD info ON `user-account` TYPE object PERMISSIONS FOR select NONE;
+                DEFINE FIELD info.bio ON `user-account` TYPE string;
+            DEFINE ACCESS account ON DATABASE TYPE RECORD
+                SIGNIN (SELECT * FROM `user-account` WHERE name = $name);
+        "#,
+        )
+        .expect("Schema construction should succeed");
+        ctx.set_access("account").expect("Access should exist");
+
+        let analyzed_kind = analyze_select(&mut ctx, "SELECT * FROM `user-account`;")
+            .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("array<{ name: string }>"));
+    }
 }
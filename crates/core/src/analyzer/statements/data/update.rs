@@ -1,8 +1,6 @@
-use surrealdb::sql::{
-    statements::UpdateStatement,
-    Data, Idiom, Kind, Value,
-};
+use surrealdb::sql::{statements::UpdateStatement, Kind, Table};
 
+use super::fields::{analyze_data, Write};
 use crate::analyzer::{
     context::AnalyzerContext,
     error::{AnalyzerError, AnalyzerResult},
@@ -22,70 +20,16 @@ pub fn analyze_update(ctx: &mut AnalyzerContext, stmt: &UpdateStatement) -> Anal
         _ => return Err(AnalyzerError::UnexpectedSyntax),
     };
 
-    // Analyze the Data variant for parameter inference
-    if let Some(data) = &stmt.data {
-        match data {
-            Data::SetExpression(sets) => {
-                for (idiom, _op, value) in sets {
-                    if let Value::Param(param_name) = value {
-                        // For SET expressions, infer from the field being set
-                        ctx.infer_param_from_field(table_name, idiom, param_name)?;
-                    }
-                }
-            }
-            Data::ContentExpression(value) => {
-                match value {
-                    Value::Param(param_name) => {
-                        // For CONTENT $param, infer the full table type
-                        ctx.infer_param_from_table(table_name, param_name)?;
-                    }
-                    Value::Object(obj) => {
-                        // For CONTENT { field: $param }, infer each field's type
-                        for (field, value) in obj.iter() {
-                            if let Value::Param(param_name) = value {
-                                let field_idiom = Idiom::from(field.clone());
-                                ctx.infer_param_from_field(table_name, &field_idiom, param_name)?;
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-            Data::MergeExpression(value)
-            | Data::PatchExpression(value)
-            | Data::ReplaceExpression(value) => {
-                match value {
-                    Value::Param(param_name) => {
-                        // For MERGE/PATCH/REPLACE $param, infer the full table type
-                        ctx.infer_param_from_table(table_name, param_name)?;
-                    }
-                    Value::Object(obj) => {
-                        // For MERGE/PATCH/REPLACE { field: $param }, infer each field's type
-                        for (field, value) in obj.iter() {
-                            if let Value::Param(param_name) = value {
-                                let field_idiom = Idiom::from(field.clone());
-                                ctx.infer_param_from_field(table_name, &field_idiom, param_name)?;
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-            Data::UpdateExpression(updates) => {
-                for (idiom, _op, value) in updates {
-                    if let Value::Param(param_name) = value {
-                        ctx.infer_param_from_field(table_name, idiom, param_name)?;
-                    }
-                }
-            }
-            Data::EmptyExpression
-            | Data::UnsetExpression(_)
-            | Data::SingleExpression(_)
-            | Data::ValuesExpression(_) => {}
-            _ => todo!(),
-        }
+    // Values are evaluated against the record being written.
+    let document = Kind::Record(vec![Table::from(table_name.clone())]);
+
+    if let Some(cond) = &stmt.cond {
+        ctx.resolve_in_document(&document, &cond.0)?;
     }
 
+    // Infer parameters from, and check values against, the fields they're written to.
+    analyze_data(ctx, table_name, stmt.data.as_ref(), Write::Update)?;
+
     let target_type = ctx.build_full_table_type(table_name)?;
     Ok(Kind::Array(Box::new(target_type), None))
 }
@@ -204,7 +148,7 @@ mod tests {
         let stmt = "UPDATE user SET name = 'John';";
         let analyzed_kind = analyze(&mut ctx, stmt).expect("Analysis should succeed");
 
-        let expected_kind = kind!("array<array<{ name: string, age: number }>>");
+        let expected_kind = kind!("[array<{ name: string, age: number }>]");
         assert_eq!(analyzed_kind, expected_kind);
     }
 }
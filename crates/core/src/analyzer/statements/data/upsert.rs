@@ -1,8 +1,9 @@
+use super::fields::{analyze_data, Write};
 use crate::analyzer::{
     context::AnalyzerContext,
     error::{AnalyzerError, AnalyzerResult},
 };
-use surrealdb::sql::{statements::UpsertStatement, Kind};
+use surrealdb::sql::{statements::UpsertStatement, Kind, Table};
 
 /// Analyzes an UPSERT statement.
 ///
@@ -11,16 +12,26 @@ use surrealdb::sql::{statements::UpsertStatement, Kind};
 pub fn analyze_upsert(ctx: &mut AnalyzerContext, stmt: &UpsertStatement) -> AnalyzerResult<Kind> {
     // Resolve the table (using the first value in the `what` clause)
     let kind = ctx.resolve(&stmt.what.0[0])?;
-    let target_type = match kind {
+    let table_name = match kind {
         Kind::Record(tables) => {
             if let Some(table) = tables.first() {
-                ctx.build_full_table_type(&table.0)?
+                table.0.clone()
             } else {
                 return Err(AnalyzerError::UnexpectedSyntax);
             }
         }
         _ => return Err(AnalyzerError::UnexpectedSyntax),
     };
+
+    if let Some(cond) = &stmt.cond {
+        let document = Kind::Record(vec![Table::from(table_name.clone())]);
+        ctx.resolve_in_document(&document, &cond.0)?;
+    }
+
+    // Infer parameters from, and check values against, the fields they're written to.
+    analyze_data(ctx, &table_name, stmt.data.as_ref(), Write::Upsert)?;
+
+    let target_type = ctx.build_full_table_type(&table_name)?;
     // UPSERT returns an array of the updated records.
     Ok(Kind::Array(Box::new(target_type), None))
 }
@@ -47,7 +58,7 @@ mod tests {
         let stmt = "UPSERT user SET name = 'Jane';";
         let analyzed_kind = analyze(&mut ctx, stmt).expect("Analysis should succeed");
 
-        let expected_kind = kind!("array<array<{ name: string, age: number }>>");
+        let expected_kind = kind!("[array<{ name: string, age: number }>]");
         assert_eq!(analyzed_kind, expected_kind);
     }
 }
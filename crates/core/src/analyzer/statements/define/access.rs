@@ -0,0 +1,204 @@
+use std::collections::BTreeMap;
+
+use surrealdb::sql::{statements::DefineAccessStatement, AccessType, Subquery, Value};
+
+use crate::analyzer::{
+    context::{AccessMethod, AnalyzerContext},
+    error::AnalyzerResult,
+};
+
+/// Analyzes a DEFINE ACCESS statement such as:
+///
+/// ```sql
+/// DEFINE ACCESS account ON DATABASE TYPE RECORD
+///     SIGNUP (CREATE user CONTENT { email: $email, pass: crypto::argon2::generate($pass) })
+///     SIGNIN (SELECT * FROM user WHERE email = $email AND crypto::argon2::compare(pass, $pass));
+/// ```
+///
+/// Legacy `DEFINE SCOPE` statements are parsed as record access methods too.
+/// The table read by SIGNIN (or written by SIGNUP) is the table `$auth`
+/// points to once the access method is selected with
+/// [`AnalyzerContext::set_access`]. Tokens of `TYPE JWT` methods are issued
+/// elsewhere, so their custom claims come from
+/// [`AnalyzerContext::register_claims`].
+pub fn analyze_define_access(
+    ctx: &mut AnalyzerContext,
+    stmt: &DefineAccessStatement,
+) -> AnalyzerResult<()> {
+    let table = match &stmt.kind {
+        AccessType::Record(record) => record
+            .signin
+            .as_ref()
+            .and_then(record_table)
+            .or_else(|| record.signup.as_ref().and_then(record_table)),
+        _ => None,
+    };
+
+    let method = AccessMethod {
+        table,
+        external: matches!(stmt.kind, AccessType::Jwt(_)),
+        claims: BTreeMap::new(),
+    };
+    ctx.register_access(&stmt.name, method);
+    Ok(())
+}
+
+/// Finds the table a SIGNIN or SIGNUP query produces records from.
+fn record_table(value: &Value) -> Option<String> {
+    let Value::Subquery(subquery) = value else {
+        return None;
+    };
+    let what = match &**subquery {
+        Subquery::Select(stmt) => &stmt.what,
+        Subquery::Create(stmt) => &stmt.what,
+        Subquery::Upsert(stmt) => &stmt.what,
+        _ => return None,
+    };
+
+    what.0.iter().find_map(|value| match value {
+        Value::Table(table) => Some(table.0.clone()),
+        Value::Thing(thing) => Some(thing.tb.clone()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn auth_from_record_access() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD email ON user TYPE string;
+                DEFINE FIELD pass ON user TYPE string;
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD title ON post TYPE string;
+                DEFINE FIELD author ON post TYPE record<user>;
+            DEFINE ACCESS account ON DATABASE TYPE RECORD
+                SIGNUP (CREATE user CONTENT { email: $email, pass: crypto::argon2::generate($pass) })
+                SIGNIN (SELECT * FROM user WHERE email = $email AND crypto::argon2::compare(pass, $pass));
+        "#,
+        )
+        .expect("Schema construction should succeed");
+        ctx.set_access("account").expect("Access should exist");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            SELECT email FROM $auth;
+            RETURN $auth.id;
+            SELECT title, author.email AS email FROM post WHERE author = $auth;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!(
+                "[array<{ email: string }>, record<user>, array<{ email: string, title: string }>]"
+            )
+        );
+        assert!(ctx.get_all_inferred_params().is_empty());
+    }
+
+    #[test]
+    fn auth_from_legacy_scope() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD email ON user TYPE string;
+            DEFINE SCOPE account SESSION 24h
+                SIGNIN (SELECT * FROM user WHERE email = $email);
+        "#,
+        )
+        .expect("Schema construction should succeed");
+        ctx.set_access("account").expect("Access should exist");
+
+        let analyzed_kind =
+            analyze(&mut ctx, "RETURN $auth.email;").expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[string]"));
+    }
+
+    #[test]
+    fn token_from_record_access() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD email ON user TYPE string;
+                DEFINE FIELD pass ON user TYPE string;
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD title ON post TYPE string;
+                DEFINE FIELD author ON post TYPE record<user>;
+            DEFINE ACCESS account ON DATABASE TYPE RECORD
+                SIGNUP (CREATE user CONTENT { email: $email, pass: crypto::argon2::generate($pass) })
+                SIGNIN (SELECT * FROM user WHERE email = $email AND crypto::argon2::compare(pass, $pass));
+        "#,
+        )
+        .expect("Schema construction should succeed");
+        ctx.set_access("account").expect("Access should exist");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            "SELECT AC, ID FROM $token; SELECT title FROM post WHERE author = $token.ID;",
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[array<{ AC: string, ID: record<user> }>, array<{ title: string }>]")
+        );
+    }
+
+    #[test]
+    fn token_with_custom_claims() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            "DEFINE ACCESS service ON DATABASE TYPE JWT ALGORITHM HS512 KEY 'secret';",
+        )
+        .expect("Schema construction should succeed");
+        ctx.register_claims("service", [("tenant".to_string(), kind!("string"))])
+            .expect("Access should exist");
+        ctx.set_access("service").expect("Access should exist");
+
+        let analyzed_kind = analyze(&mut ctx, "RETURN $token.tenant; RETURN $token.exp;")
+            .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[string, option<int>]"));
+    }
+
+    #[test]
+    fn auth_without_access() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD email ON user TYPE string;
+            DEFINE TABLE post SCHEMAFULL PERMISSIONS FOR select WHERE author = $auth.id;
+                DEFINE FIELD author ON post TYPE record<user>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        // Without an access method `$auth` may be anything, but can't be selected from.
+        let analyzed_kind =
+            analyze(&mut ctx, "RETURN $auth.email;").expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[any]"));
+
+        let err = analyze(&mut ctx, "SELECT * FROM $auth;").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::MissingAuth));
+
+        let err = ctx
+            .set_access("admin")
+            .expect_err("Access should not exist");
+        assert!(matches!(err, AnalyzerError::AccessNotFound(_)));
+    }
+}
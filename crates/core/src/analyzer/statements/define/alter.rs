@@ -0,0 +1,91 @@
+use surrealdb::sql::{statements::AlterStatement, Kind};
+
+use crate::analyzer::{
+    context::AnalyzerContext,
+    error::{AnalyzerError, AnalyzerResult},
+};
+
+/// Analyzes an ALTER statement such as:
+///
+/// ```sql
+/// ALTER TABLE user SCHEMAFULL PERMISSIONS FOR select FULL;
+/// ```
+///
+/// The clauses given replace those of the table's definition, and the others
+/// are kept. Altering an undefined table is an error unless IF EXISTS is given.
+/// An ALTER statement returns nothing.
+pub fn analyze_alter(ctx: &mut AnalyzerContext, stmt: &AlterStatement) -> AnalyzerResult<Kind> {
+    let AlterStatement::Table(stmt) = stmt else {
+        return Err(AnalyzerError::Unimplemented(format!(
+            "Analysis not implemented for {}",
+            stmt
+        )));
+    };
+
+    let Some(table) = ctx.find_table_definition_mut(&stmt.name) else {
+        return match stmt.if_exists {
+            true => Ok(Kind::Null),
+            false => Err(AnalyzerError::TableNotFound(stmt.name.to_string())),
+        };
+    };
+
+    if let Some(full) = stmt.full {
+        table.full = full;
+    }
+    if let Some(drop) = stmt.drop {
+        table.drop = drop;
+    }
+    if let Some(kind) = &stmt.kind {
+        table.kind = kind.clone();
+    }
+    if let Some(permissions) = &stmt.permissions {
+        table.permissions = permissions.clone();
+        ctx.register_permission(&stmt.name, permissions.clone());
+    }
+    Ok(Kind::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn alter_schemafull() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMALESS;
+                DEFINE FIELD name ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(&mut ctx, "CREATE user SET name = 'Tobie', age = 33;")
+            .expect("Analysis should succeed");
+
+        analyze(&mut ctx, "ALTER TABLE user SCHEMAFULL;").expect("Analysis should succeed");
+        let err = analyze(&mut ctx, "CREATE user SET name = 'Tobie', age = 33;")
+            .expect_err("Analysis should fail");
+        assert!(err.is_schema_violation());
+    }
+
+    #[test]
+    fn alter_undefined() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMALESS;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let err =
+            analyze(&mut ctx, "ALTER TABLE post SCHEMAFULL;").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TableNotFound(_)));
+
+        analyze(&mut ctx, "ALTER TABLE IF EXISTS post SCHEMAFULL;")
+            .expect("Analysis should succeed");
+    }
+}
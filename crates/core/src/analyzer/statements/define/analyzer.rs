@@ -0,0 +1,109 @@
+use surrealdb::sql::statements::DefineAnalyzerStatement;
+
+use crate::analyzer::{
+    context::{AnalyzerContext, TextAnalyzer},
+    error::{AnalyzerError, AnalyzerResult},
+};
+
+/// Analyzes a DEFINE ANALYZER statement such as:
+///
+/// ```sql
+/// DEFINE ANALYZER english TOKENIZERS blank, class FILTERS lowercase, snowball(english);
+/// ```
+///
+/// The analyzer is registered with its tokenizers and filters, so the SEARCH
+/// indexes and `search::analyze` calls naming it can be checked. A FUNCTION
+/// applied to the text must be defined.
+pub fn analyze_define_analyzer(
+    ctx: &mut AnalyzerContext,
+    stmt: &DefineAnalyzerStatement,
+) -> AnalyzerResult<()> {
+    let function = stmt.function.as_ref().map(|function| function.to_string());
+    if let Some(function) = &function {
+        if ctx.get_function(function).is_none() {
+            return Err(AnalyzerError::FunctionNotFound(format!("fn::{}", function)));
+        }
+    }
+
+    ctx.register_analyzer(
+        &stmt.name,
+        TextAnalyzer {
+            function,
+            tokenizers: stmt
+                .tokenizers
+                .iter()
+                .flatten()
+                .map(ToString::to_string)
+                .collect(),
+            filters: stmt
+                .filters
+                .iter()
+                .flatten()
+                .map(ToString::to_string)
+                .collect(),
+        },
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn analyzer_registered() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE ANALYZER english TOKENIZERS blank, class FILTERS lowercase, snowball(english);
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzer = ctx.get_analyzer("english").expect("Analyzer should exist");
+        assert_eq!(analyzer.tokenizers.len(), 2);
+        assert_eq!(analyzer.filters.len(), 2);
+    }
+
+    #[test]
+    fn undefined_analyzers() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE ANALYZER english TOKENIZERS blank, class FILTERS lowercase, snowball(english);
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD title ON post TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE INDEX title ON post FIELDS title SEARCH ANALYZER english BM25;
+            RETURN search::analyze('english', 'Hello world');
+        "#,
+        )
+        .expect("Analysis should succeed");
+
+        for stmt in [
+            "DEFINE INDEX title_fr ON post FIELDS title SEARCH ANALYZER french BM25;",
+            "RETURN search::analyze('french', 'Bonjour');",
+        ] {
+            let err = analyze(&mut ctx, stmt).expect_err("Analysis should fail");
+            assert!(
+                matches!(err, AnalyzerError::AnalyzerNotFound(_)),
+                "{stmt} should be rejected"
+            );
+        }
+
+        let err = analyze(
+            &mut ctx,
+            "DEFINE ANALYZER custom FUNCTION fn::strip TOKENIZERS blank;",
+        )
+        .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::FunctionNotFound(_)));
+    }
+}
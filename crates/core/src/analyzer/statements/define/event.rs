@@ -0,0 +1,147 @@
+use surrealdb::sql::{statements::DefineEventStatement, Kind, Literal, Table};
+
+use crate::analyzer::{
+    context::{AnalyzerContext, Role},
+    error::{AnalyzerError, AnalyzerResult},
+    kind::{union_of, KindExt},
+};
+
+/// Analyzes a DEFINE EVENT statement such as:
+///
+/// ```sql
+/// DEFINE EVENT email ON user WHEN $before.email != $after.email THEN (
+///     CREATE log SET user = $value.id, previous = $before.email, current = $after.email
+/// );
+/// ```
+///
+/// The WHEN condition and the THEN expressions are evaluated on the record that
+/// triggered the event, with `$value` bound to the record itself and `$event` to
+/// the kind of change. `$before` is NONE when the record is created, and `$after`
+/// when it is deleted. The WHEN condition must be a boolean.
+pub fn analyze_define_event(
+    ctx: &mut AnalyzerContext,
+    stmt: &DefineEventStatement,
+) -> AnalyzerResult<()> {
+    let table = stmt.what.as_str();
+    if ctx.find_table_definition(table).is_none() {
+        return Err(AnalyzerError::schema_violation(
+            format!("Event '{}' is defined on an undefined table", stmt.name),
+            Some(table),
+            None::<String>,
+        ));
+    }
+
+    let record = ctx.build_full_table_type(table)?;
+    let event = Kind::Either(
+        ["CREATE", "UPDATE", "DELETE"]
+            .into_iter()
+            .map(|event| Kind::Literal(Literal::String(event.into())))
+            .collect(),
+    );
+
+    // As with function bodies, parameters used by the event aren't parameters
+    // of the query defining it. Events are run by the database itself, whatever
+    // the access of the query triggering them.
+    let mut body_ctx = ctx.clone();
+    body_ctx.set_role(Role::Owner);
+    body_ctx.push_scope();
+    body_ctx.bind_param("before", union_of([record.clone(), Kind::Null]));
+    body_ctx.bind_param("after", union_of([record.clone(), Kind::Null]));
+    body_ctx.bind_param("value", record);
+    body_ctx.bind_param("event", event);
+
+    let document = Kind::Record(vec![Table::from(table)]);
+    let when = body_ctx.resolve_in_document(&document, &stmt.when)?;
+    if !Kind::Bool.is_assignable_from(&when.without_none()) {
+        return Err(AnalyzerError::type_mismatch(&Kind::Bool, &when));
+    }
+    for then in stmt.then.iter() {
+        body_ctx.resolve_in_document(&document, then)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn event_bindings() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD email ON user TYPE string;
+            DEFINE TABLE log SCHEMAFULL;
+                DEFINE FIELD user ON log TYPE record<user>;
+                DEFINE FIELD event ON log TYPE string;
+                DEFINE FIELD previous ON log TYPE option<string>;
+                DEFINE FIELD current ON log TYPE option<string>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE EVENT email ON user WHEN $event = "UPDATE" AND $before.email != $after.email THEN (
+                CREATE log SET user = $value.id, event = $event, previous = $before.email, current = $value.email
+            );
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert!(ctx.get_all_inferred_params().is_empty());
+    }
+
+    #[test]
+    fn invalid_event_bodies() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD email ON user TYPE string;
+            DEFINE TABLE log SCHEMAFULL;
+                DEFINE FIELD user ON log TYPE record<user>;
+                DEFINE FIELD event ON log TYPE string;
+                DEFINE FIELD previous ON log TYPE option<string>;
+                DEFINE FIELD current ON log TYPE option<string>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let err = analyze(
+            &mut ctx,
+            "DEFINE EVENT email ON user WHEN $after.mail != NONE THEN (CREATE log SET user = $value.id, event = $event);",
+        )
+        .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::FieldNotFound { .. }));
+
+        let err = analyze(
+            &mut ctx,
+            "DEFINE EVENT email ON user THEN (CREATE log SET user = $after.id, event = 1);",
+        )
+        .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
+
+        // `$before` is NONE for created records.
+        let err = analyze(
+            &mut ctx,
+            "DEFINE EVENT email ON user THEN (CREATE log SET user = $value.id, event = $event, previous = $before);",
+        )
+        .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
+
+        let err = analyze(
+            &mut ctx,
+            "DEFINE EVENT email ON user WHEN $after.email THEN (CREATE log SET user = $value.id, event = $event);",
+        )
+        .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
+
+        let err = analyze(&mut ctx, "DEFINE EVENT email ON usr THEN (CREATE log);")
+            .expect_err("Analysis should fail");
+        assert!(err.is_schema_violation());
+    }
+}
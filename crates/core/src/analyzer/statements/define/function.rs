@@ -0,0 +1,155 @@
+use surrealdb::sql::{statements::DefineFunctionStatement, Kind};
+
+use crate::analyzer::{
+    context::{AnalyzerContext, FunctionSignature},
+    error::{AnalyzerError, AnalyzerResult},
+    kind::KindExt,
+    statements::logic::analyze_block,
+};
+
+/// Analyzes a DEFINE FUNCTION statement such as:
+///
+/// ```sql
+/// DEFINE FUNCTION fn::greet($name: string) -> string {
+///     RETURN "Hello, " + $name;
+/// };
+/// ```
+///
+/// The body is analyzed with each argument bound to its declared kind. The
+/// function returns its declared kind, which the body must satisfy, or the kind
+/// inferred from the body when none is declared. The resulting signature is
+/// registered so calls to the function can be checked.
+pub fn analyze_define_function(
+    ctx: &mut AnalyzerContext,
+    stmt: &DefineFunctionStatement,
+) -> AnalyzerResult<()> {
+    let name = stmt.name.0.as_str();
+    let args: Vec<(String, Kind)> = stmt
+        .args
+        .iter()
+        .map(|(ident, kind)| (ident.0.clone(), kind.clone()))
+        .collect();
+
+    // The body is analyzed in a context of its own, so that parameters it uses
+    // aren't reported as parameters of the query defining it. The function is
+    // registered up front so it can call itself.
+    let mut body_ctx = ctx.clone();
+    body_ctx.register_function(
+        name,
+        FunctionSignature {
+            args: args.clone(),
+            returns: stmt.returns.clone().unwrap_or(Kind::Any),
+        },
+    );
+    body_ctx.push_scope();
+    for (arg, kind) in &args {
+        body_ctx.bind_param(arg, kind.clone());
+    }
+    let body = analyze_block(&mut body_ctx, &stmt.block)?;
+
+    let returns = match &stmt.returns {
+        Some(declared) => {
+            if !declared.is_assignable_from(&body) {
+                return Err(AnalyzerError::type_mismatch(declared, &body));
+            }
+            declared.clone()
+        }
+        None => body,
+    };
+
+    ctx.register_function(name, FunctionSignature { args, returns });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealdb::sql::Kind;
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn return_kind_from_body() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+            DEFINE FUNCTION fn::adults($min: int, $limit: option<int>) {
+                RETURN SELECT name FROM user WHERE age >= $min;
+            };
+            DEFINE FUNCTION fn::shout($text: string) -> string {
+                RETURN string::uppercase($text) + '!';
+            };
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind =
+            analyze(&mut ctx, "fn::adults(18); fn::shout('hi');").expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[array<{ name: string }>, string]"));
+    }
+
+    #[test]
+    fn infers_call_site_params() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+            DEFINE FUNCTION fn::adults($min: int, $limit: option<int>) {
+                RETURN SELECT name FROM user WHERE age >= $min;
+            };
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(&mut ctx, "fn::adults($min, $limit);").expect("Analysis should succeed");
+        assert_eq!(
+            ctx.get_all_inferred_params(),
+            &[
+                ("min".to_string(), Kind::Int),
+                ("limit".to_string(), Kind::Option(Box::new(Kind::Int)))
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_calls() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE FUNCTION fn::shout($text: string) -> string {
+                RETURN string::uppercase($text) + '!';
+            };
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let err = analyze(&mut ctx, "fn::shout();").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::InvalidFunctionCall { .. }));
+
+        let err = analyze(&mut ctx, "fn::shout('a', 'b');").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::InvalidFunctionCall { .. }));
+
+        let err = analyze(&mut ctx, "fn::shout(42);").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn body_must_match_declared_return() {
+        let mut ctx = AnalyzerContext::new();
+
+        let err = analyze(
+            &mut ctx,
+            "DEFINE FUNCTION fn::answer() -> string { RETURN 42; };",
+        )
+        .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
+    }
+}
@@ -0,0 +1,229 @@
+use surrealdb::sql::{index::Index, statements::DefineIndexStatement, Kind};
+
+use crate::analyzer::{
+    context::{AnalyzerContext, IndexKind, TableIndex},
+    error::{AnalyzerError, AnalyzerResult},
+    kind::KindExt,
+};
+
+/// Analyzes a DEFINE INDEX statement such as:
+///
+/// ```sql
+/// DEFINE INDEX body ON post FIELDS body SEARCH ANALYZER simple BM25 HIGHLIGHTS;
+/// DEFINE INDEX embedding ON post FIELDS embedding MTREE DIMENSION 3 DIST COSINE;
+/// ```
+///
+/// Every indexed field must be defined. Full-text indexes cover a single string
+/// field with a defined analyzer, and vector indexes a single array of numbers
+/// whose length, when declared, matches the index dimension. The index is
+/// registered so the operators searching it can be checked.
+pub fn analyze_define_index(
+    ctx: &mut AnalyzerContext,
+    stmt: &DefineIndexStatement,
+) -> AnalyzerResult<()> {
+    let table = stmt.what.as_str();
+    if ctx.find_table_definition(table).is_none() {
+        return Err(AnalyzerError::TableNotFound(table.to_string()));
+    }
+
+    let mut kinds = Vec::new();
+    for field in stmt.cols.iter() {
+        let kind = ctx
+            .get_field_definitions(table)
+            .into_iter()
+            .find(|def| def.name == *field)
+            .ok_or_else(|| AnalyzerError::field_not_found(field.to_string(), table))?
+            .kind
+            .clone()
+            .unwrap_or(Kind::Any);
+        kinds.push(kind);
+    }
+
+    let kind = match &stmt.index {
+        Index::Idx => IndexKind::Plain,
+        Index::Uniq => IndexKind::Unique,
+        Index::Search(params) => {
+            let field = single_field(stmt, &kinds, "A SEARCH index covers a single field")?;
+            let strings = Kind::Array(Box::new(Kind::String), None);
+            let kind = field.without_none();
+            if !Kind::String.is_assignable_from(&kind) && !strings.is_assignable_from(&kind) {
+                return Err(violation(stmt, "A SEARCH index needs a string field"));
+            }
+            ctx.check_analyzer(&params.az)?;
+            IndexKind::Search {
+                analyzer: params.az.to_string(),
+                highlights: params.hl,
+            }
+        }
+        Index::MTree(params) => {
+            check_vector(stmt, &kinds, params.dimension)?;
+            IndexKind::MTree {
+                dimension: params.dimension,
+                distance: params.distance.clone(),
+            }
+        }
+        Index::Hnsw(params) => {
+            check_vector(stmt, &kinds, params.dimension)?;
+            IndexKind::Hnsw {
+                dimension: params.dimension,
+                distance: params.distance.clone(),
+            }
+        }
+        index => {
+            return Err(AnalyzerError::Unimplemented(format!(
+                "Analysis not implemented for {} indexes",
+                index
+            )))
+        }
+    };
+
+    ctx.register_index(TableIndex {
+        name: stmt.name.to_string(),
+        table: table.to_string(),
+        fields: stmt.cols.0.clone(),
+        kind,
+    });
+    Ok(())
+}
+
+/// Checks that a vector index covers a single array of numbers, with
+/// `dimension` elements if its length is declared.
+fn check_vector(stmt: &DefineIndexStatement, kinds: &[Kind], dimension: u16) -> AnalyzerResult<()> {
+    let field = single_field(stmt, kinds, "A vector index covers a single field")?;
+    match field.without_none() {
+        Kind::Any => Ok(()),
+        Kind::Array(inner, len) | Kind::Set(inner, len)
+            if *inner == Kind::Any || inner.is_numeric() =>
+        {
+            match len {
+                Some(len) if len != u64::from(dimension) => Err(violation(
+                    stmt,
+                    format!("The field holds {len} dimensions, but the index has {dimension}"),
+                )),
+                _ => Ok(()),
+            }
+        }
+        _ => Err(violation(stmt, "A vector index needs an array of numbers")),
+    }
+}
+
+/// Gets the kind of the only field of an index that must cover a single one.
+fn single_field<'a>(
+    stmt: &DefineIndexStatement,
+    kinds: &'a [Kind],
+    message: &str,
+) -> AnalyzerResult<&'a Kind> {
+    match kinds {
+        [kind] => Ok(kind),
+        _ => Err(violation(stmt, message)),
+    }
+}
+
+fn violation(stmt: &DefineIndexStatement, message: impl Into<String>) -> AnalyzerError {
+    AnalyzerError::schema_violation(
+        message,
+        Some(stmt.what.as_str()),
+        Some(stmt.cols.to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn invalid_indexes() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE ANALYZER ascii TOKENIZERS class FILTERS lowercase, ascii;
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD slug ON post TYPE string;
+                DEFINE FIELD title ON post TYPE string;
+                DEFINE FIELD body ON post TYPE string;
+                DEFINE FIELD views ON post TYPE int;
+                DEFINE FIELD embedding ON post TYPE array<float, 3>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let err = analyze(&mut ctx, "DEFINE INDEX author ON post FIELDS author;")
+            .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::FieldNotFound { .. }));
+
+        let err = analyze(&mut ctx, "DEFINE INDEX slug ON article FIELDS slug UNIQUE;")
+            .expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TableNotFound(_)));
+
+        for stmt in [
+            "DEFINE INDEX views ON post FIELDS views SEARCH ANALYZER ascii BM25;",
+            "DEFINE INDEX text ON post FIELDS title, body SEARCH ANALYZER ascii BM25;",
+            "DEFINE INDEX views ON post FIELDS views MTREE DIMENSION 3;",
+            "DEFINE INDEX embedding_hnsw ON post FIELDS embedding HNSW DIMENSION 4;",
+        ] {
+            let err = analyze(&mut ctx, stmt).expect_err("Analysis should fail");
+            assert!(err.is_schema_violation(), "{stmt} should be rejected");
+        }
+    }
+
+    #[test]
+    fn index_operators() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE ANALYZER ascii TOKENIZERS class FILTERS lowercase, ascii;
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD slug ON post TYPE string;
+                DEFINE FIELD title ON post TYPE string;
+                DEFINE FIELD body ON post TYPE string;
+                DEFINE FIELD embedding ON post TYPE array<float, 3>;
+                DEFINE FIELD summary ON post TYPE array<float>;
+            DEFINE INDEX slug ON post FIELDS slug UNIQUE;
+            DEFINE INDEX title ON post FIELDS title SEARCH ANALYZER ascii BM25;
+            DEFINE INDEX body ON post FIELDS body SEARCH ANALYZER ascii BM25 HIGHLIGHTS;
+            DEFINE INDEX embedding ON post FIELDS embedding MTREE DIMENSION 3 DIST COSINE;
+            DEFINE INDEX summary ON post FIELDS summary HNSW DIMENSION 4 DIST EUCLIDEAN;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            r#"
+            SELECT title, search::score(1) AS score, search::highlight('<b>', '</b>', 2) AS body
+                FROM post WHERE title @1@ 'surreal' OR body @2@ 'database';
+            SELECT slug FROM post WHERE embedding <|2|> [0.1, 0.2, 0.3];
+            SELECT slug FROM post WHERE summary <|2,40|> $summary;
+        "#,
+        )
+        .expect("Analysis should succeed");
+
+        for stmt in [
+            // Fields without a matching index.
+            "SELECT slug FROM post WHERE slug @@ 'surreal';",
+            "SELECT slug FROM post WHERE summary <|2|> [0.1, 0.2, 0.3, 0.4];",
+            "SELECT slug FROM post WHERE embedding <|2,40|> [0.1, 0.2, 0.3];",
+            // A vector of the wrong dimension.
+            "SELECT slug FROM post WHERE embedding <|2|> [0.1, 0.2];",
+            // A match reference naming two searches.
+            "SELECT slug FROM post WHERE title @1@ 'surreal' AND body @1@ 'database';",
+        ] {
+            let err = analyze(&mut ctx, stmt).expect_err("Analysis should fail");
+            assert!(err.is_schema_violation(), "{stmt} should be rejected");
+        }
+
+        // Scores need a match reference, and highlights an index storing them.
+        for stmt in [
+            "SELECT search::score(1) AS score FROM post WHERE title @@ 'surreal';",
+            "SELECT search::highlight('<b>', '</b>', 1) AS title FROM post WHERE title @1@ 'surreal';",
+        ] {
+            let err = analyze(&mut ctx, stmt).expect_err("Analysis should fail");
+            assert!(
+                matches!(err, AnalyzerError::InvalidFunctionCall { .. }),
+                "{stmt} should be rejected"
+            );
+        }
+    }
+}
@@ -0,0 +1,153 @@
+mod access; // DEFINE ACCESS statements
+mod alter; // ALTER statements
+mod analyzer; // DEFINE ANALYZER statements
+mod event; // DEFINE EVENT statements
+mod function; // DEFINE FUNCTION statements
+mod index; // DEFINE INDEX statements
+mod param; // DEFINE PARAM statements
+mod remove; // REMOVE statements
+mod table; // DEFINE TABLE statements
+
+use surrealdb::sql::{statements::DefineStatement, Kind};
+
+use crate::analyzer::{
+    context::{field_path, AnalyzerContext, DefinitionKey},
+    error::{AnalyzerError, AnalyzerResult},
+};
+
+pub use access::analyze_define_access;
+pub use alter::analyze_alter;
+pub use analyzer::analyze_define_analyzer;
+pub use event::analyze_define_event;
+pub use function::analyze_define_function;
+pub use index::analyze_define_index;
+pub use param::analyze_define_param;
+pub use remove::analyze_remove;
+pub use table::analyze_define_table;
+
+/// Analyzes a DEFINE statement and records the definition in the context.
+///
+/// Definitions that carry SurrealQL of their own, such as function bodies, are
+/// analyzed before they are recorded. Redefining something replaces it with
+/// OVERWRITE, keeps it with IF NOT EXISTS and is an error otherwise. A DEFINE
+/// statement returns nothing.
+pub fn analyze_define(ctx: &mut AnalyzerContext, stmt: &DefineStatement) -> AnalyzerResult<Kind> {
+    if let Some(key) = DefinitionKey::of(stmt).filter(|key| ctx.is_defined(key)) {
+        match clauses(stmt) {
+            (true, _) => return Ok(Kind::Null),
+            (_, true) => {}
+            _ => return Err(AnalyzerError::DuplicateDefinition(key.to_string())),
+        }
+    }
+
+    match stmt {
+        DefineStatement::Access(access_stmt) => analyze_define_access(ctx, access_stmt)?,
+        DefineStatement::Analyzer(analyzer_stmt) => analyze_define_analyzer(ctx, analyzer_stmt)?,
+        DefineStatement::Function(function_stmt) => analyze_define_function(ctx, function_stmt)?,
+        DefineStatement::Param(param_stmt) => analyze_define_param(ctx, param_stmt)?,
+        DefineStatement::Event(event_stmt) => analyze_define_event(ctx, event_stmt)?,
+        DefineStatement::Index(index_stmt) => analyze_define_index(ctx, index_stmt)?,
+        DefineStatement::Table(table_stmt) => analyze_define_table(ctx, table_stmt)?,
+        DefineStatement::Field(field_stmt) => ctx.register_permission(
+            &format!("{}.{}", field_stmt.what.to_raw(), field_path(&field_stmt.name)),
+            field_stmt.permissions.clone(),
+        ),
+        _ => {}
+    }
+
+    ctx.append_definition(stmt.clone());
+    Ok(Kind::Null)
+}
+
+/// Gets the IF NOT EXISTS and OVERWRITE clauses of a definition.
+fn clauses(stmt: &DefineStatement) -> (bool, bool) {
+    match stmt {
+        DefineStatement::Table(def) => (def.if_not_exists, def.overwrite),
+        DefineStatement::Field(def) => (def.if_not_exists, def.overwrite),
+        DefineStatement::Index(def) => (def.if_not_exists, def.overwrite),
+        DefineStatement::Event(def) => (def.if_not_exists, def.overwrite),
+        DefineStatement::Function(def) => (def.if_not_exists, def.overwrite),
+        DefineStatement::Param(def) => (def.if_not_exists, def.overwrite),
+        DefineStatement::Analyzer(def) => (def.if_not_exists, def.overwrite),
+        DefineStatement::Access(def) => (def.if_not_exists, def.overwrite),
+        _ => (false, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn redefinitions() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE FIELD OVERWRITE age ON user TYPE float;
+            DEFINE FIELD IF NOT EXISTS name ON user TYPE int;
+        "#,
+        )
+        .expect("Analysis should succeed");
+
+        let analyzed_kind =
+            analyze(&mut ctx, "SELECT name, age FROM user;").expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[array<{ name: string, age: float }>]")
+        );
+        assert_eq!(ctx.get_field_definitions("user").len(), 2);
+
+        for stmt in [
+            "DEFINE TABLE user SCHEMALESS;",
+            "DEFINE FIELD name ON user TYPE int;",
+        ] {
+            let err = analyze(&mut ctx, stmt).expect_err("Analysis should fail");
+            assert!(
+                matches!(err, AnalyzerError::DuplicateDefinition(_)),
+                "{stmt} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn migrations_replay_in_order() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        for migration in [
+            "DEFINE FIELD email ON user TYPE string;",
+            "REMOVE FIELD age ON user;",
+            "DEFINE FIELD age ON user TYPE option<int>;",
+        ] {
+            analyze(&mut ctx, migration).expect("Migration should apply");
+        }
+
+        let analyzed_kind =
+            analyze(&mut ctx, "SELECT * FROM user;").expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[array<{ name: string, email: string, age: option<int> }>]")
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use surrealdb::sql::statements::DefineParamStatement;
+
+use crate::analyzer::{context::AnalyzerContext, error::AnalyzerResult};
+
+/// Analyzes a DEFINE PARAM statement such as:
+///
+/// ```sql
+/// DEFINE PARAM $currency VALUE 'USD';
+/// ```
+///
+/// The parameter is registered with the kind of its value, so every query
+/// resolves it without the client having to supply it.
+pub fn analyze_define_param(
+    ctx: &mut AnalyzerContext,
+    stmt: &DefineParamStatement,
+) -> AnalyzerResult<()> {
+    let kind = ctx.resolve(&stmt.value)?;
+    ctx.define_param(&stmt.name, kind);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext};
+
+    #[test]
+    fn defined_param_resolves() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE PARAM $currency VALUE 'USD';
+            DEFINE TABLE product SCHEMAFULL;
+                DEFINE FIELD currency ON product TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            "RETURN $currency; UPDATE product SET currency = $currency;",
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[string, array<{ currency: string }>]")
+        );
+        assert!(ctx.get_all_inferred_params().is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+use surrealdb::sql::{statements::RemoveStatement, Kind};
+
+use crate::analyzer::{
+    context::{field_path, AnalyzerContext, DefinitionKey},
+    error::{AnalyzerError, AnalyzerResult},
+};
+
+/// Analyzes a REMOVE statement such as:
+///
+/// ```sql
+/// REMOVE FIELD nickname ON user;
+/// REMOVE TABLE IF EXISTS session;
+/// ```
+///
+/// The definition is removed from the context, along with what depends on it:
+/// removing a table removes its fields, indexes and events. Removing something
+/// that isn't defined is an error unless IF EXISTS is given. A REMOVE statement
+/// returns nothing.
+pub fn analyze_remove(ctx: &mut AnalyzerContext, stmt: &RemoveStatement) -> AnalyzerResult<Kind> {
+    let (key, if_exists) = match stmt {
+        RemoveStatement::Table(stmt) => (DefinitionKey::Table(stmt.name.to_raw()), stmt.if_exists),
+        RemoveStatement::Field(stmt) => (
+            DefinitionKey::Field(stmt.what.to_raw(), field_path(&stmt.name)),
+            stmt.if_exists,
+        ),
+        RemoveStatement::Index(stmt) => (
+            DefinitionKey::Index(stmt.what.to_raw(), stmt.name.to_raw()),
+            stmt.if_exists,
+        ),
+        RemoveStatement::Event(stmt) => (
+            DefinitionKey::Event(stmt.what.to_raw(), stmt.name.to_raw()),
+            stmt.if_exists,
+        ),
+        RemoveStatement::Function(stmt) => {
+            (DefinitionKey::Function(stmt.name.to_raw()), stmt.if_exists)
+        }
+        RemoveStatement::Param(stmt) => (DefinitionKey::Param(stmt.name.to_raw()), stmt.if_exists),
+        RemoveStatement::Analyzer(stmt) => {
+            (DefinitionKey::Analyzer(stmt.name.to_raw()), stmt.if_exists)
+        }
+        RemoveStatement::Access(stmt) => {
+            (DefinitionKey::Access(stmt.name.to_raw()), stmt.if_exists)
+        }
+        // Namespaces, databases, users and models aren't part of the schema model.
+        _ => return Ok(Kind::Null),
+    };
+
+    if !ctx.remove_definition(&key) && !if_exists {
+        return Err(AnalyzerError::DefinitionNotFound(key.to_string()));
+    }
+    Ok(Kind::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn remove_field() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD nickname ON user TYPE option<string>;
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD title ON post TYPE string;
+            DEFINE INDEX title ON post FIELDS title UNIQUE;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(&mut ctx, "REMOVE FIELD nickname ON user;").expect("Analysis should succeed");
+
+        let analyzed_kind =
+            analyze(&mut ctx, "SELECT * FROM user;").expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[array<{ name: string }>]"));
+
+        let err =
+            analyze(&mut ctx, "SELECT nickname FROM user;").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::FieldNotFound { .. }));
+    }
+
+    #[test]
+    fn remove_table() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD nickname ON user TYPE option<string>;
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD title ON post TYPE string;
+            DEFINE INDEX title ON post FIELDS title UNIQUE;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(&mut ctx, "REMOVE TABLE post;").expect("Analysis should succeed");
+        assert!(ctx.find_table_definition("post").is_none());
+        assert!(ctx.get_field_definitions("post").is_empty());
+        assert!(ctx.get_indexes("post").is_empty());
+
+        // The table can be defined again from scratch.
+        analyze(&mut ctx, "DEFINE TABLE post SCHEMALESS;").expect("Analysis should succeed");
+    }
+
+    #[test]
+    fn remove_undefined() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let err =
+            analyze(&mut ctx, "REMOVE FIELD email ON user;").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::DefinitionNotFound(_)));
+
+        analyze(
+            &mut ctx,
+            r#"
+            REMOVE FIELD IF EXISTS email ON user;
+            REMOVE TABLE IF EXISTS session;
+        "#,
+        )
+        .expect("Analysis should succeed");
+    }
+}
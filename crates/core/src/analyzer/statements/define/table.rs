@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use surrealdb::sql::{
+    statements::{DefineTableStatement, SelectStatement},
+    Kind, Literal, Value, View,
+};
+
+use crate::analyzer::{
+    context::{AnalyzerContext, Role},
+    error::{AnalyzerError, AnalyzerResult},
+    statements::data::analyze_select,
+};
+
+/// Analyzes a DEFINE TABLE statement such as:
+///
+/// ```sql
+/// DEFINE TABLE user SCHEMAFULL PERMISSIONS FOR select FULL;
+/// DEFINE TABLE stats AS SELECT count() AS total, country FROM user GROUP BY country;
+/// ```
+///
+/// The table's PERMISSIONS are registered. A table defined AS SELECT is a view:
+/// its fields are the projections of the SELECT, and it can't be written to.
+pub fn analyze_define_table(
+    ctx: &mut AnalyzerContext,
+    stmt: &DefineTableStatement,
+) -> AnalyzerResult<()> {
+    ctx.register_permission(&stmt.name, stmt.permissions.clone());
+
+    match &stmt.view {
+        Some(view) => {
+            let fields = view_fields(ctx, stmt, view)?;
+            ctx.register_view(&stmt.name, fields);
+        }
+        None => {
+            ctx.remove_view(&stmt.name);
+        }
+    }
+    Ok(())
+}
+
+/// Derives the fields of a view from the SELECT computing it.
+fn view_fields(
+    ctx: &AnalyzerContext,
+    stmt: &DefineTableStatement,
+    view: &View,
+) -> AnalyzerResult<BTreeMap<String, Kind>> {
+    let mut select = SelectStatement::default();
+    select.expr = view.expr.clone();
+    select.what.0 = view.what.0.iter().cloned().map(Value::Table).collect();
+    select.cond = view.cond.clone();
+    select.group = view.group.clone();
+
+    // The database computes views itself, so every field of the source tables
+    // is visible to it, and parameters it uses aren't inputs of the query.
+    let mut view_ctx = ctx.clone();
+    view_ctx.set_role(Role::Owner);
+    match analyze_select(&mut view_ctx, &select)? {
+        Kind::Array(inner, _) => match *inner {
+            Kind::Literal(Literal::Object(fields)) => Ok(fields),
+            _ => Err(violation(stmt)),
+        },
+        _ => Err(violation(stmt)),
+    }
+}
+
+fn violation(stmt: &DefineTableStatement) -> AnalyzerError {
+    AnalyzerError::schema_violation(
+        "A view must SELECT fields rather than a VALUE",
+        Some(stmt.name.as_str()),
+        None::<String>,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext};
+
+    #[test]
+    fn view_types() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD country ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+            DEFINE TABLE stats AS SELECT count() AS total, country FROM user GROUP BY country;
+            DEFINE TABLE adults AS SELECT name, age FROM user WHERE age >= 18;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            SELECT * FROM stats;
+            SELECT name FROM adults WHERE age > 30;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[array<{ total: int, country: string }>, array<{ name: string }>]")
+        );
+    }
+
+    #[test]
+    fn view_fields_in_clauses() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+                DEFINE FIELD address ON user TYPE { city: string, zip: string };
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD title ON post TYPE string;
+                DEFINE FIELD author ON post TYPE record<user>;
+            DEFINE TABLE adults AS SELECT name, age, address FROM user WHERE age >= 18;
+            DEFINE TABLE published AS SELECT title, author FROM post;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            SELECT name, age FROM adults ORDER BY age DESC;
+            SELECT address.{city} FROM adults;
+            SELECT title, author FROM published FETCH author;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!(
+                "[array<{ age: int, name: string }>, array<{ address: { city: string } }>, array<{ title: string, author: { address: { city: string, zip: string }, age: int, name: string } }>]"
+            )
+        );
+    }
+
+    #[test]
+    fn view_writes() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD country ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+            DEFINE TABLE stats AS SELECT count() AS total, country FROM user GROUP BY country;
+            DEFINE TABLE adults AS SELECT name, age FROM user WHERE age >= 18;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        for stmt in [
+            "CREATE stats SET total = 1, country = 'UK';",
+            "UPDATE adults SET age = 20;",
+            "DELETE stats;",
+        ] {
+            let err = analyze(&mut ctx, stmt).expect_err("Analysis should fail");
+            assert!(err.is_schema_violation(), "{stmt} should be rejected");
+        }
+
+        // Redefining the table as a regular one makes it writable again.
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE OVERWRITE adults SCHEMALESS;
+            UPDATE adults SET age = 20;
+        "#,
+        )
+        .expect("Analysis should succeed");
+    }
+}
@@ -0,0 +1,246 @@
+use surrealdb::sql::{
+    statements::IfelseStatement, Block, Entry, Kind, Literal, Statement, Subquery, Value,
+};
+
+use crate::analyzer::{
+    context::AnalyzerContext,
+    error::{AnalyzerError, AnalyzerResult},
+    kind::union_of,
+    statements::{data, define},
+};
+
+/// Analyzes a block such as:
+///
+/// ```sql
+/// {
+///     LET $name = string::uppercase($input);
+///     RETURN $name;
+/// }
+/// ```
+///
+/// Every entry is analyzed in a scope of its own, so parameters bound within the
+/// block don't outlive it. A RETURN leaves the block with its value, even when
+/// it is nested in an IF or a FOR, so the block evaluates to the union of the
+/// values it may RETURN and of its last entry, if evaluation reaches it.
+pub fn analyze_block(ctx: &mut AnalyzerContext, block: &Block) -> AnalyzerResult<Kind> {
+    let mut returns = Vec::new();
+    let end = analyze_scoped(ctx, &block.0, &mut returns)?;
+    Ok(union_of(returns.into_iter().chain(end)))
+}
+
+/// Analyzes `entries` in a scope of their own, see [`analyze_entries`].
+fn analyze_scoped(
+    ctx: &mut AnalyzerContext,
+    entries: &[Entry],
+    returns: &mut Vec<Kind>,
+) -> AnalyzerResult<Option<Kind>> {
+    ctx.push_scope();
+    let kind = analyze_entries(ctx, entries, returns);
+    ctx.pop_scope();
+    kind
+}
+
+/// Analyzes the entries of a block, adding the kind of every value they may
+/// RETURN to `returns`. Evaluates to the kind of the last entry, or to `None` if
+/// evaluation never gets past a RETURN or THROW.
+fn analyze_entries(
+    ctx: &mut AnalyzerContext,
+    entries: &[Entry],
+    returns: &mut Vec<Kind>,
+) -> AnalyzerResult<Option<Kind>> {
+    let mut kind = Kind::Null;
+    for entry in entries {
+        let entry_kind = match entry {
+            Entry::Value(value) => analyze_value(ctx, value, returns)?,
+            Entry::Set(set_stmt) => Some(super::analyze_set(ctx, set_stmt)?),
+            Entry::Ifelse(ifelse_stmt) => {
+                super::ifelse::analyze_branches(ctx, ifelse_stmt, returns)?
+            }
+            Entry::Select(select_stmt) => Some(data::analyze_select(ctx, select_stmt)?),
+            Entry::Create(create_stmt) => Some(data::analyze_create(ctx, create_stmt)?),
+            Entry::Update(update_stmt) => Some(data::analyze_update(ctx, update_stmt)?),
+            Entry::Delete(delete_stmt) => Some(data::analyze_delete(ctx, delete_stmt)?),
+            Entry::Relate(relate_stmt) => Some(data::analyze_relate(ctx, relate_stmt)?),
+            Entry::Insert(insert_stmt) => Some(data::analyze_insert(ctx, insert_stmt)?),
+            Entry::Upsert(upsert_stmt) => Some(data::analyze_upsert(ctx, upsert_stmt)?),
+            Entry::Output(output_stmt) => {
+                returns.push(super::analyze_output(ctx, output_stmt)?);
+                None
+            }
+            Entry::Throw(throw_stmt) => {
+                ctx.resolve(&throw_stmt.error)?;
+                None
+            }
+            // The definition is checked, but the block's definitions only exist
+            // when it runs, so they don't change the schema being analyzed.
+            Entry::Define(define_stmt) => {
+                Some(define::analyze_define(&mut ctx.clone(), define_stmt)?)
+            }
+            Entry::Foreach(foreach_stmt) => {
+                let element = match ctx.resolve(&foreach_stmt.range)? {
+                    Kind::Array(inner, _) | Kind::Set(inner, _) => *inner,
+                    Kind::Literal(Literal::Array(items)) => union_of(items),
+                    _ => Kind::Any,
+                };
+                ctx.push_scope();
+                ctx.bind_param(&foreach_stmt.param, element);
+                let body = analyze_scoped(ctx, &foreach_stmt.block.0, returns);
+                ctx.pop_scope();
+                // The range may be empty, so evaluation carries on regardless.
+                body?;
+                Some(Kind::Null)
+            }
+            Entry::Break(_) | Entry::Continue(_) => Some(Kind::Null),
+            _ => {
+                return Err(AnalyzerError::Unimplemented(format!(
+                    "Analysis not implemented for {:?}",
+                    entry
+                )))
+            }
+        };
+        // Nothing after a RETURN or THROW is evaluated.
+        match entry_kind {
+            Some(entry_kind) => kind = entry_kind,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(kind))
+}
+
+/// Analyzes a value evaluated as part of a block, see [`analyze_entries`].
+/// RETURNs within a nested block leave the enclosing one too, while those
+/// within a subquery only leave the subquery.
+pub(super) fn analyze_value(
+    ctx: &mut AnalyzerContext,
+    value: &Value,
+    returns: &mut Vec<Kind>,
+) -> AnalyzerResult<Option<Kind>> {
+    match value {
+        Value::Block(block) => analyze_scoped(ctx, &block.0, returns),
+        _ => {
+            let kind = ctx.resolve(value)?;
+            Ok((!always_throws(value)).then_some(kind))
+        }
+    }
+}
+
+/// Returns true if evaluating `stmt` always ends in a RETURN (or a THROW),
+/// which ends the transaction it is part of.
+pub fn always_returns(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Output(_) => true,
+        Statement::Ifelse(stmt) => ifelse_exit(stmt) == Exit::Returns,
+        _ => false,
+    }
+}
+
+/// Returns true if evaluating `value` always ends in a THROW, so it never
+/// produces a result of its own.
+pub fn always_throws(value: &Value) -> bool {
+    value_exit(value) == Exit::Throws
+}
+
+/// How evaluating some SurrealQL ends.
+#[derive(Clone, Copy, PartialEq)]
+enum Exit {
+    /// Evaluation may carry on to whatever follows.
+    Continues,
+    /// Evaluation always leaves the enclosing block, and may RETURN a value.
+    Returns,
+    /// Evaluation always ends in a THROW.
+    Throws,
+}
+
+fn value_exit(value: &Value) -> Exit {
+    match value {
+        Value::Block(block) => entries_exit(&block.0),
+        Value::Subquery(subquery) => match &**subquery {
+            Subquery::Ifelse(stmt) => ifelse_exit(stmt),
+            _ => Exit::Continues,
+        },
+        _ => Exit::Continues,
+    }
+}
+
+/// Walks `entries` up to the first one that always leaves the block.
+fn entries_exit(entries: &[Entry]) -> Exit {
+    for entry in entries {
+        let exit = match entry {
+            Entry::Throw(_) => Exit::Throws,
+            Entry::Output(_) => Exit::Returns,
+            Entry::Ifelse(stmt) => ifelse_exit(stmt),
+            Entry::Value(value) => value_exit(value),
+            _ => Exit::Continues,
+        };
+        if exit != Exit::Continues {
+            return exit;
+        }
+    }
+    Exit::Continues
+}
+
+/// An IF statement leaves the block only if every branch does, and without an
+/// ELSE branch it may not run any of them.
+fn ifelse_exit(stmt: &IfelseStatement) -> Exit {
+    let Some(close) = &stmt.close else {
+        return Exit::Continues;
+    };
+    let exits = stmt
+        .exprs
+        .iter()
+        .map(|(_, then)| then)
+        .chain([close])
+        .map(value_exit);
+    exits.fold(Exit::Throws, |all, exit| match (all, exit) {
+        (Exit::Continues, _) | (_, Exit::Continues) => Exit::Continues,
+        (Exit::Returns, _) | (_, Exit::Returns) => Exit::Returns,
+        (Exit::Throws, Exit::Throws) => Exit::Throws,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn nested_returns() {
+        let mut ctx = AnalyzerContext::new();
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            { IF $a { RETURN 1 }; 'x' };
+            { IF $a { RETURN 1 } ELSE { THROW 'b' }; 'unreachable' };
+            { FOR $item IN [1, 2] { IF $item > 1 { RETURN true } }; { RETURN 'x' } };
+            { LET $value = { RETURN 1 }; 'x' };
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[int | string, int, bool | string, string]")
+        );
+    }
+
+    #[test]
+    fn definitions_stay_in_block() {
+        let mut ctx = AnalyzerContext::new();
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            {
+                DEFINE TABLE temp SCHEMALESS;
+                RETURN 1;
+            };
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[int]"));
+
+        let err = analyze(&mut ctx, "SELECT * FROM temp;").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TableNotFound(_)));
+    }
+}
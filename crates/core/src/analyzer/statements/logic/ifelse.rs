@@ -0,0 +1,151 @@
+use surrealdb::sql::{statements::IfelseStatement, Kind, Value};
+
+use crate::analyzer::{
+    context::AnalyzerContext,
+    error::{AnalyzerError, AnalyzerResult},
+    kind::{union_of, KindExt},
+};
+
+use super::block::analyze_value;
+
+/// Analyzes an IF statement such as:
+///
+/// ```sql
+/// IF $user.admin { 'admin' } ELSE IF $user.verified { 'user' } ELSE { NONE };
+/// ```
+///
+/// Every condition must be boolean compatible, and every branch is analyzed. The
+/// statement evaluates to the normalized union of its branch kinds; without an
+/// ELSE branch the statement may also evaluate to NONE. Branches that always
+/// THROW never produce a value and don't contribute to the union, while those
+/// that RETURN contribute the value they return.
+pub fn analyze_ifelse(ctx: &mut AnalyzerContext, stmt: &IfelseStatement) -> AnalyzerResult<Kind> {
+    let mut returns = Vec::new();
+    let kind = analyze_branches(ctx, stmt, &mut returns)?;
+    Ok(union_of(returns.into_iter().chain(kind)))
+}
+
+/// Analyzes the branches of an IF statement within a block, adding the kind of
+/// every value they may RETURN from the block to `returns`. Evaluates to the
+/// union of the branches that don't RETURN or THROW, or to `None` if none.
+pub(super) fn analyze_branches(
+    ctx: &mut AnalyzerContext,
+    stmt: &IfelseStatement,
+    returns: &mut Vec<Kind>,
+) -> AnalyzerResult<Option<Kind>> {
+    let mut branches = Vec::new();
+
+    for (cond, then) in &stmt.exprs {
+        analyze_condition(ctx, cond)?;
+        branches.extend(analyze_value(ctx, then, returns)?);
+    }
+
+    match &stmt.close {
+        Some(close) => branches.extend(analyze_value(ctx, close, returns)?),
+        None => branches.push(Kind::Null),
+    }
+
+    Ok((!branches.is_empty()).then(|| union_of(branches)))
+}
+
+fn analyze_condition(ctx: &mut AnalyzerContext, cond: &Value) -> AnalyzerResult<()> {
+    let expected = Kind::Option(Box::new(Kind::Bool));
+
+    // A parameter used as a condition must be a boolean.
+    ctx.infer_param(cond, &Kind::Bool, &format!("IF {}", cond))?;
+
+    let found = ctx.resolve(cond)?;
+    if !expected.is_assignable_from(&found) {
+        return Err(AnalyzerError::type_mismatch(&Kind::Bool, &found));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealdb::sql::Kind;
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn branches_form_union() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD age ON user TYPE int;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            IF $admin {
+                SELECT * FROM user;
+            } ELSE IF 1 > 2 {
+                'nothing'
+            } ELSE {
+                THROW 'unreachable';
+            };
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[array<{ name: string, age: int }> | string]")
+        );
+        assert_eq!(
+            ctx.get_all_inferred_params(),
+            &[("admin".to_string(), Kind::Bool)]
+        );
+    }
+
+    #[test]
+    fn throwing_branches() {
+        let mut ctx = AnalyzerContext::new();
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            IF $a {
+                THROW 'a';
+                true;
+            } ELSE IF $b {
+                IF $c { THROW 'c' } ELSE { THROW 'd' };
+            } ELSE IF $d {
+                RETURN 'returned';
+                THROW 'unreachable';
+            } ELSE {
+                1
+            };
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[string | int]"));
+    }
+
+    #[test]
+    fn identical_branches_collapse() {
+        let mut ctx = AnalyzerContext::new();
+
+        let analyzed_kind =
+            analyze(&mut ctx, "IF true THEN 'a' ELSE 'b' END;").expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[string]"));
+
+        let analyzed_kind = analyze(&mut ctx, "IF true { 1 } ELSE IF false { 2 };")
+            .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[option<int>]"));
+    }
+
+    #[test]
+    fn non_boolean_condition() {
+        let mut ctx = AnalyzerContext::new();
+
+        let err = analyze(&mut ctx, "IF 'yes' { 1 };").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
+    }
+}
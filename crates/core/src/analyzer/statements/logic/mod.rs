@@ -0,0 +1,9 @@
+mod block; // Blocks of statements
+mod ifelse; // IF ELSE statements
+mod output; // RETURN statements
+mod set; // LET statements
+
+pub use block::{always_returns, analyze_block};
+pub use ifelse::analyze_ifelse;
+pub use output::analyze_output;
+pub use set::analyze_set;
@@ -0,0 +1,84 @@
+use surrealdb::sql::{statements::OutputStatement, Kind};
+
+use crate::analyzer::{
+    context::AnalyzerContext,
+    error::AnalyzerResult,
+    statements::data::{fetches_to_chain, KindFetchExt},
+};
+
+/// Analyzes a RETURN statement such as:
+///
+/// ```sql
+/// RETURN (SELECT * FROM post) FETCH author;
+/// ```
+///
+/// The statement evaluates to its value, with any fetched record links expanded
+/// to the full type of the linked table.
+pub fn analyze_output(ctx: &mut AnalyzerContext, stmt: &OutputStatement) -> AnalyzerResult<Kind> {
+    let kind = ctx.resolve(&stmt.what)?;
+    Ok(match &stmt.fetch {
+        Some(fetches) => kind.resolve_fetch(&fetches_to_chain(fetches), ctx),
+        None => kind,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext};
+
+    #[test]
+    fn return_with_fetch() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD title ON post TYPE string;
+                DEFINE FIELD author ON post TYPE record<user>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(&mut ctx, "RETURN (SELECT * FROM post) FETCH author;")
+            .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[array<{ title: string, author: { name: string } }>]")
+        );
+    }
+
+    #[test]
+    fn return_short_circuits_block() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+            DEFINE TABLE post SCHEMAFULL;
+                DEFINE FIELD title ON post TYPE string;
+                DEFINE FIELD author ON post TYPE record<user>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            LET $title = 'Hello';
+            {
+                LET $post = (CREATE ONLY post SET title = $title, author = user:jane);
+                RETURN $post.title;
+                SELECT * FROM user;
+            };
+            RETURN 42;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[null, string, int]"));
+    }
+}
@@ -0,0 +1,125 @@
+use surrealdb::sql::{statements::SetStatement, Kind};
+
+use crate::analyzer::{
+    context::AnalyzerContext,
+    error::{AnalyzerError, AnalyzerResult},
+    kind::KindExt,
+};
+
+/// Analyzes a LET statement such as:
+///
+/// ```sql
+/// LET $org = (SELECT * FROM ONLY org:acme);
+/// LET $limit: int = 10;
+/// ```
+///
+/// The parameter is bound to the kind of its value in the current scope, so later
+/// statements resolve `$org` through the binding. A typed LET checks its value
+/// against the declared kind, and binds the parameter to that kind. Bound parameters
+/// are not external inputs, and are never reported as inferred parameters. The
+/// statement itself returns nothing.
+pub fn analyze_set(ctx: &mut AnalyzerContext, stmt: &SetStatement) -> AnalyzerResult<Kind> {
+    let kind = match &stmt.kind {
+        Some(expected) => {
+            let found = ctx.resolve_as(&stmt.what, expected)?;
+            if !expected.is_assignable_from(&found) {
+                return Err(AnalyzerError::type_mismatch(expected, &found));
+            }
+            expected.clone()
+        }
+        None => ctx.resolve(&stmt.what)?,
+    };
+    ctx.bind_param(&stmt.name, kind);
+    Ok(Kind::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealdb::sql::Kind;
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext, error::AnalyzerError};
+
+    #[test]
+    fn bound_param_resolves() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE org SCHEMAFULL;
+                DEFINE FIELD name ON org TYPE string;
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+                DEFINE FIELD org ON user TYPE record<org>;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            LET $org = (SELECT * FROM ONLY org:acme);
+            SELECT name FROM user WHERE org = $org.id;
+            RETURN $org.name;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(
+            analyzed_kind,
+            kind!("[null, array<{ name: string }>, string]")
+        );
+    }
+
+    #[test]
+    fn bound_param_is_not_inferred() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        analyze(
+            &mut ctx,
+            r#"
+            LET $name = 'Acme';
+            UPDATE user SET name = $name;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert!(ctx.get_all_inferred_params().is_empty());
+
+        analyze(&mut ctx, "UPDATE user SET name = $name;").expect("Analysis should succeed");
+        assert_eq!(
+            ctx.get_all_inferred_params(),
+            &[("name".to_string(), Kind::String)]
+        );
+    }
+
+    #[test]
+    fn typed_binding() {
+        let mut ctx = AnalyzerContext::new();
+
+        let analyzed_kind = analyze(
+            &mut ctx,
+            r#"
+            LET $age: number = 42;
+            LET $name: option<string> = $input;
+            $age;
+            $name;
+        "#,
+        )
+        .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[null, null, number, option<string>]"));
+        assert_eq!(
+            ctx.get_all_inferred_params(),
+            &[("input".to_string(), kind!("option<string>"))]
+        );
+
+        let err = analyze(&mut ctx, "LET $age: int = 'a';").expect_err("Analysis should fail");
+        assert!(matches!(err, AnalyzerError::TypeMismatch { .. }));
+    }
+}
@@ -3,21 +3,23 @@
 //! This module contains analyzers for different categories of SurrealQL statements:
 //!
 //! - Data manipulation (SELECT, CREATE, UPDATE, DELETE)
-//! - Schema definition (DEFINE TABLE, DEFINE FIELD)
+//! - Logic and control flow (LET, IF ELSE, RETURN, blocks)
+//! - Schema definition (DEFINE TABLE, DEFINE FIELD, DEFINE FUNCTION)
 //! - System commands (INFO, USE)
 //!
 //! Each statement type has its own submodule with specialized analysis logic
 //! that validates the statement against schema rules and determines result types.
 
 pub(crate) mod data;
-// pub(crate) mod logic;
+pub(crate) mod define;
+pub(crate) mod logic;
 // pub(crate) mod system;
 
 use crate::analyzer::{
     context::AnalyzerContext,
     error::{AnalyzerError, AnalyzerResult},
 };
-use surrealdb::sql::{Kind, Statement};
+use surrealdb::sql::{Kind, Statement, Subquery};
 
 /// Analyzes a single SurrealQL statement.
 ///
@@ -43,13 +45,15 @@ pub fn analyze_statement(ctx: &mut AnalyzerContext, stmt: &Statement) -> Analyze
         Statement::Upsert(upsert_stmt) => self::data::analyze_upsert(ctx, upsert_stmt),
         Statement::Relate(relate_stmt) => self::data::analyze_relate(ctx, relate_stmt),
 
-        // Schema definition statements
-        Statement::Define(define_stmt) => {
-            ctx.append_definition(define_stmt.clone());
+        // Logic statements
+        Statement::Set(set_stmt) => self::logic::analyze_set(ctx, set_stmt),
+        Statement::Ifelse(ifelse_stmt) => self::logic::analyze_ifelse(ctx, ifelse_stmt),
+        Statement::Output(output_stmt) => self::logic::analyze_output(ctx, output_stmt),
 
-            //A define statement returns nothing.
-            Ok(Kind::Null)
-        }
+        // Schema definition statements
+        Statement::Define(define_stmt) => self::define::analyze_define(ctx, define_stmt),
+        Statement::Remove(remove_stmt) => self::define::analyze_remove(ctx, remove_stmt),
+        Statement::Alter(alter_stmt) => self::define::analyze_alter(ctx, alter_stmt),
         // Other statement types
         _ => Err(AnalyzerError::Surreal(
             surrealdb::err::Error::Unimplemented(format!(
@@ -59,3 +63,59 @@ pub fn analyze_statement(ctx: &mut AnalyzerContext, stmt: &Statement) -> Analyze
         )),
     }
 }
+
+/// Analyzes a parenthesized subquery such as `(SELECT * FROM post)`.
+///
+/// The subquery is analyzed like the statement it wraps. If it appears within
+/// a statement that binds `$this`, that document is available to the subquery
+/// as `$parent`.
+pub fn analyze_subquery(ctx: &mut AnalyzerContext, subquery: &Subquery) -> AnalyzerResult<Kind> {
+    ctx.push_scope();
+    if let Some(this) = ctx.get_param("this").cloned() {
+        ctx.bind_param("parent", this);
+    }
+
+    let kind = match subquery {
+        Subquery::Value(value) => ctx.resolve(value),
+        Subquery::Output(output_stmt) => self::logic::analyze_output(ctx, output_stmt),
+        Subquery::Ifelse(ifelse_stmt) => self::logic::analyze_ifelse(ctx, ifelse_stmt),
+        Subquery::Select(select_stmt) => self::data::analyze_select(ctx, select_stmt),
+        Subquery::Update(update_stmt) => self::data::analyze_update(ctx, update_stmt),
+        Subquery::Create(create_stmt) => self::data::analyze_create(ctx, create_stmt),
+        Subquery::Delete(delete_stmt) => self::data::analyze_delete(ctx, delete_stmt),
+        Subquery::Insert(insert_stmt) => self::data::analyze_insert(ctx, insert_stmt),
+        Subquery::Upsert(upsert_stmt) => self::data::analyze_upsert(ctx, upsert_stmt),
+        Subquery::Relate(relate_stmt) => self::data::analyze_relate(ctx, relate_stmt),
+        _ => Err(AnalyzerError::Unimplemented(format!(
+            "Analysis not implemented for subquery {}",
+            subquery
+        ))),
+    };
+
+    ctx.pop_scope();
+    kind
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealguard_macros::kind;
+
+    use crate::analyzer::{analyze, context::AnalyzerContext};
+
+    #[test]
+    fn return_subquery() {
+        let mut ctx = AnalyzerContext::new();
+        analyze(
+            &mut ctx,
+            r#"
+            DEFINE TABLE user SCHEMAFULL;
+                DEFINE FIELD name ON user TYPE string;
+        "#,
+        )
+        .expect("Schema construction should succeed");
+
+        let analyzed_kind = analyze(&mut ctx, "RETURN (UPDATE user SET name = 'Jane');")
+            .expect("Analysis should succeed");
+        assert_eq!(analyzed_kind, kind!("[array<{ name: string }>]"));
+    }
+}